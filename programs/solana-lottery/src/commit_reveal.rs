@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+use crate::errors::LotteryError;
+use sha2::{Sha256, Digest};
+
+// Commit-reveal randomness. The operator commits `sha256(secret)` in
+// `schedule_draw`; the reveal is mixed with the hash of the first slot at or
+// after `commit_slot + REVEAL_DELAY_SLOTS`, which nobody knows at commit time.
+// SlotHashes only retains the last 512 slots, so the reveal window stays
+// inside that horizon; missing it forfeits the operator bond.
+
+pub const REVEAL_DELAY_SLOTS: u64 = 10;
+pub const REVEAL_WINDOW_SLOTS: u64 = 450;
+
+const SLOT_HASH_ENTRY_SIZE: usize = 8 + 32; // (slot, hash)
+
+pub fn compute_commitment(secret: &[u8; 32]) -> [u8; 32] {
+    Sha256::digest(secret).into()
+}
+
+#[inline]
+pub fn target_slot(commit_slot: u64) -> Result<u64> {
+    Ok(commit_slot
+        .checked_add(REVEAL_DELAY_SLOTS)
+        .ok_or(LotteryError::ArithmeticError)?)
+}
+
+/// Finds the hash of the earliest recorded slot at or after `target`.
+/// Entries are stored newest first, so the last match wins.
+pub fn find_slot_hash(slot_hashes: &AccountInfo, target: u64) -> Result<[u8; 32]> {
+    let data = slot_hashes.try_borrow_data()?;
+    let len_bytes: [u8; 8] = data
+        .get(..8)
+        .ok_or(LotteryError::SlotHashUnavailable)?
+        .try_into()
+        .map_err(|_| LotteryError::SlotHashUnavailable)?;
+    let len = u64::from_le_bytes(len_bytes) as usize;
+
+    let mut found = None;
+    for i in 0..len {
+        let start = 8 + i * SLOT_HASH_ENTRY_SIZE;
+        let entry = data
+            .get(start..start + SLOT_HASH_ENTRY_SIZE)
+            .ok_or(LotteryError::SlotHashUnavailable)?;
+        let slot = u64::from_le_bytes(entry[..8].try_into().unwrap());
+        if slot < target {
+            break;
+        }
+        found = Some(start + 8);
+    }
+
+    let offset = found.ok_or(LotteryError::SlotHashUnavailable)?;
+    let mut hash = [0u8; 32];
+    hash.copy_from_slice(&data[offset..offset + 32]);
+    Ok(hash)
+}
+
+pub fn mix(secret: &[u8; 32], slot_hash: &[u8; 32], lottery: &Pubkey) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(secret);
+    hasher.update(slot_hash);
+    hasher.update(lottery.as_ref());
+    hasher.finalize().into()
+}
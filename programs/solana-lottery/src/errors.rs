@@ -14,6 +14,8 @@ pub enum LotteryError {
     InvalidLotteryState,
     #[msg("Invalid lottery type")]
     InvalidLotteryType,
+    #[msg("Lottery start time not reached")]
+    LotteryNotStarted,
 
     // Prize errors
     #[msg("Invalid prize claim attempt")]
@@ -26,6 +28,44 @@ pub enum LotteryError {
     InvalidPrizeDistribution,
     #[msg("Claim window expired")]
     ClaimWindowExpired,
+    #[msg("Invalid prize table")]
+    InvalidPrizeTable,
+    #[msg("Winner registration window closed")]
+    RegistrationClosed,
+    #[msg("Winner registration window still open")]
+    RegistrationOpen,
+    #[msg("Ticket already registered")]
+    TicketAlreadyRegistered,
+    #[msg("Ticket not registered as a winner")]
+    TicketNotRegistered,
+    #[msg("Invalid claim batch size")]
+    InvalidBatchSize,
+    #[msg("Every ticket has already been paid out")]
+    PayoutsComplete,
+
+    // Governance errors
+    #[msg("Invalid governance proposal")]
+    InvalidProposal,
+    #[msg("Proposal is not queued")]
+    ProposalNotQueued,
+    #[msg("Proposal delay has not elapsed")]
+    ProposalNotReady,
+    #[msg("Lottery parameters are frozen once tickets are sold")]
+    ParametersFrozen,
+
+    // Ticket errors
+    #[msg("Invalid ticket numbers")]
+    InvalidTicketNumbers,
+    #[msg("Invalid number domain")]
+    InvalidNumberDomain,
+    #[msg("Ticket does not belong to this lottery round")]
+    InvalidTicket,
+    #[msg("Ticket already refunded")]
+    TicketAlreadyRefunded,
+    #[msg("Refund window expired")]
+    RefundWindowExpired,
+    #[msg("Refund window still open")]
+    RefundWindowOpen,
 
     // Treasury errors
     #[msg("Invalid treasury withdrawal")]
@@ -34,6 +74,14 @@ pub enum LotteryError {
     TreasuryError,
     #[msg("Treasury is timelocked")]
     TimelockActive,
+    #[msg("Invalid multisig signer set or threshold")]
+    InvalidMultisig,
+    #[msg("Signer already approved this proposal")]
+    AlreadyApproved,
+    #[msg("Approval threshold not met")]
+    ThresholdNotMet,
+    #[msg("Proposal already executed")]
+    ProposalExecuted,
     #[msg("Insufficient treasury balance")]
     InsufficientTreasuryBalance,
 
@@ -44,6 +92,12 @@ pub enum LotteryError {
     InvalidTokenTransfer,
     #[msg("Insufficient funds for operation")]
     InsufficientFunds,
+    #[msg("Vault does not belong to this lottery or treasury")]
+    InvalidVault,
+    #[msg("Token account mint does not match the accepted mint")]
+    InvalidMint,
+    #[msg("Mint decimals do not match ticket pricing")]
+    InvalidMintDecimals,
 
     // Validation errors
     #[msg("Exceeded ticket purchase limit")]
@@ -56,10 +110,36 @@ pub enum LotteryError {
     Unauthorized,
     #[msg("Unauthorized signer")]
     UnauthorizedSigner,
+    #[msg("Invalid protocol config")]
+    InvalidConfig,
+    #[msg("Operation paused by protocol admin")]
+    ProtocolPaused,
+    #[msg("Token mint not allowed")]
+    MintNotAllowed,
+    #[msg("Oracle not registered")]
+    OracleNotRegistered,
     #[msg("Price data is stale")]
     StalePrice,
+    #[msg("Maximum price age must be positive")]
+    InvalidMaxPriceAge,
     #[msg("Invalid Pyth price feed")]
     InvalidPythFeed,
+    #[msg("Draw method does not match the lottery randomness source")]
+    InvalidRandomnessSource,
+    #[msg("Invalid VRF proof")]
+    InvalidVrfProof,
+    #[msg("Revealed secret does not match the commitment")]
+    InvalidReveal,
+    #[msg("Reveal target slot not reached")]
+    RevealNotReady,
+    #[msg("Reveal window expired")]
+    RevealWindowExpired,
+    #[msg("Reveal window still open")]
+    RevealWindowOpen,
+    #[msg("Slot hash for the reveal target is unavailable")]
+    SlotHashUnavailable,
+    #[msg("Invalid operator bond")]
+    InvalidBond,
     #[msg("Arithmetic error")]
     ArithmeticError,
 }
\ No newline at end of file
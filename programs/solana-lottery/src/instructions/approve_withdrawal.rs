@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+use crate::state::treasury::{Treasury, WithdrawalProposal};
+use crate::errors::LotteryError;
+
+#[event]
+pub struct WithdrawalApproved {
+    pub proposal_id: u64,
+    pub signer: Pubkey,
+    pub approval_count: u8,
+    pub threshold: u8,
+}
+
+#[derive(Accounts)]
+pub struct ApproveWithdrawal<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(
+        seeds = [b"treasury"],
+        bump = treasury.bump,
+        constraint = treasury.is_authorized_signer(&signer) @ LotteryError::UnauthorizedSigner
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    #[account(
+        mut,
+        seeds = [b"withdrawal", treasury.key().as_ref(), proposal.id.to_le_bytes().as_ref()],
+        bump = proposal.bump,
+        constraint = !proposal.executed @ LotteryError::ProposalExecuted
+    )]
+    pub proposal: Account<'info, WithdrawalProposal>,
+}
+
+pub fn handler(ctx: Context<ApproveWithdrawal>) -> Result<()> {
+    let treasury = &ctx.accounts.treasury;
+    let signer = ctx.accounts.signer.key();
+    let index = treasury.signer_index(&signer).ok_or(LotteryError::UnauthorizedSigner)?;
+
+    let proposal = &mut ctx.accounts.proposal;
+    require!(proposal.approve(index)?, LotteryError::AlreadyApproved);
+
+    emit!(WithdrawalApproved {
+        proposal_id: proposal.id,
+        signer,
+        approval_count: proposal.approval_count,
+        threshold: treasury.threshold,
+    });
+
+    Ok(())
+}
@@ -1,33 +1,67 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Token, TokenAccount, Transfer};
 use crate::state::lottery::{Lottery, LotteryState};
+use crate::state::ticket::Ticket;
+use crate::state::config::ProtocolConfig;
 use crate::errors::LotteryError;
-use crate::utils;
+use crate::numbers;
+
+#[event]
+pub struct TicketPurchased {
+    pub lottery_id: u64,
+    pub ticket_index: u64,
+    pub owner: Pubkey,
+    pub numbers: [u8; 6],
+    pub bonus_number: Option<u8>,
+    pub slot: u64,
+}
 
 #[derive(Accounts)]
 pub struct BuyTicket<'info> {
     #[account(mut)]
     pub buyer: Signer<'info>,
 
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused.ticket_sales @ LotteryError::ProtocolPaused
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+
     #[account(
         mut,
-        seeds = [b"lottery", lottery.lottery_type.discriminant().to_le_bytes().as_ref()],
+        seeds = [b"lottery", lottery.authority.as_ref(), lottery.id.to_le_bytes().as_ref()],
         bump = lottery.bump,
         constraint = lottery.state == LotteryState::Open @ LotteryError::LotteryNotActive,
         constraint = Clock::get()?.unix_timestamp < lottery.timing.end_time @ LotteryError::LotteryNotActive
     )]
     pub lottery: Account<'info, Lottery>,
 
+    #[account(
+        init,
+        payer = buyer,
+        space = Ticket::SPACE,
+        seeds = [
+            b"ticket",
+            lottery.key().as_ref(),
+            lottery.id.to_le_bytes().as_ref(),
+            lottery.state_data.total_tickets.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub ticket: Account<'info, Ticket>,
+
     #[account(
         mut,
         constraint = buyer_token_account.owner == buyer.key(),
-        constraint = buyer_token_account.mint == lottery_token_account.mint
+        constraint = buyer_token_account.mint == lottery.config.mint @ LotteryError::InvalidMint
     )]
     pub buyer_token_account: Account<'info, TokenAccount>,
 
     #[account(
         mut,
-        constraint = lottery_token_account.owner == lottery.key()
+        address = lottery.config.vault @ LotteryError::InvalidVault,
+        constraint = config.is_mint_allowed(&lottery.config.mint) @ LotteryError::MintNotAllowed
     )]
     pub lottery_token_account: Account<'info, TokenAccount>,
 
@@ -36,17 +70,16 @@ pub struct BuyTicket<'info> {
     pub clock: Sysvar<'info, Clock>,
 }
 
-pub fn handler(ctx: Context<BuyTicket>, amount: u8) -> Result<()> {
+pub fn handler(ctx: Context<BuyTicket>, numbers: [u8; 6], bonus_number: Option<u8>) -> Result<()> {
     let lottery = &mut ctx.accounts.lottery;
-    
-    // Validate ticket purchase
-    utils::validate_ticket_purchase(amount)?;
-    
-    // Calculate total cost
-    let total_cost = (lottery.config.ticket_price as u128)
-        .checked_mul(amount as u128)
-        .ok_or(LotteryError::ArithmeticError)? as u64;
-    
+    let clock = &ctx.accounts.clock;
+
+    // Range check, plus distinct picks for pick-k-of-n domains
+    lottery.config.number_domain.validate_ticket(&numbers)?;
+    numbers::validate_bonus(&lottery.config.bonus_domain, bonus_number)?;
+
+    let ticket_price = lottery.config.ticket_price;
+
     // Transfer tokens
     token::transfer(
         CpiContext::new(
@@ -57,17 +90,37 @@ pub fn handler(ctx: Context<BuyTicket>, amount: u8) -> Result<()> {
                 authority: ctx.accounts.buyer.to_account_info(),
             },
         ),
-        total_cost
+        ticket_price
     )?;
-    
+
+    // Record the ticket
+    let ticket = &mut ctx.accounts.ticket;
+    ticket.lottery = lottery.key();
+    ticket.owner = ctx.accounts.buyer.key();
+    ticket.round = lottery.id;
+    ticket.index = lottery.state_data.total_tickets;
+    ticket.numbers = numbers;
+    ticket.bonus_number = bonus_number;
+    ticket.purchase_slot = clock.slot;
+    ticket.bump = ctx.bumps.ticket;
+
     // Update lottery state
     lottery.state_data.total_tickets = lottery.state_data.total_tickets
-        .checked_add(amount as u64)
+        .checked_add(1)
         .ok_or(LotteryError::ArithmeticError)?;
-        
+
     lottery.state_data.current_pool_amount = lottery.state_data.current_pool_amount
-        .checked_add(total_cost)
+        .checked_add(ticket_price)
         .ok_or(LotteryError::ArithmeticError)?;
-    
+
+    emit!(TicketPurchased {
+        lottery_id: lottery.id,
+        ticket_index: ticket.index,
+        owner: ticket.owner,
+        numbers,
+        bonus_number,
+        slot: clock.slot,
+    });
+
     Ok(())
-}
\ No newline at end of file
+}
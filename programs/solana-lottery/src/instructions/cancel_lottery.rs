@@ -0,0 +1,69 @@
+use anchor_lang::prelude::*;
+use crate::state::lottery::{CancelReason, Lottery, LotteryState, RandomnessSource};
+use crate::state::config::ProtocolConfig;
+use crate::errors::LotteryError;
+use crate::utils::REFUND_WINDOW;
+
+#[event]
+pub struct LotteryCancelled {
+    pub lottery_id: u64,
+    pub reason: CancelReason,
+    pub previous_state: LotteryState,
+    pub new_state: LotteryState,
+    pub timestamp: i64,
+}
+
+#[derive(Accounts)]
+pub struct CancelLottery<'info> {
+    #[account(
+        mut,
+        seeds = [b"lottery", lottery.authority.as_ref(), lottery.id.to_le_bytes().as_ref()],
+        bump = lottery.bump,
+        constraint = matches!(lottery.state, LotteryState::Open | LotteryState::Drawing) @ LotteryError::InvalidLotteryState
+    )]
+    pub lottery: Account<'info, Lottery>,
+
+    pub authority: Signer<'info>,
+
+    // The protocol admin may cancel any round; while draws are paused only
+    // the admin decides what happens to rounds in flight
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = lottery.authority == authority.key() || config.is_admin(&authority.key()) @ LotteryError::Unauthorized,
+        constraint = !config.paused.draws || config.is_admin(&authority.key()) @ LotteryError::ProtocolPaused
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+
+    pub clock: Sysvar<'info, Clock>,
+}
+
+pub fn handler(ctx: Context<CancelLottery>, reason: CancelReason) -> Result<()> {
+    let lottery = &mut ctx.accounts.lottery;
+    let clock = &ctx.accounts.clock;
+    let previous_state = lottery.state;
+
+    // Once a commit-reveal round has its commitment, cancelling is the same as
+    // withholding the reveal, so the bond goes to ticket holders as in `slash_bond`
+    if previous_state == LotteryState::Drawing
+        && lottery.config.randomness == RandomnessSource::CommitReveal
+    {
+        lottery.state = LotteryState::Slashed;
+    } else {
+        lottery.state = LotteryState::Refunding;
+    }
+    lottery.timing.refund_deadline = clock.unix_timestamp
+        .checked_add(REFUND_WINDOW)
+        .ok_or(LotteryError::ArithmeticError)?;
+    lottery.cancel_reason = Some(reason);
+
+    emit!(LotteryCancelled {
+        lottery_id: lottery.id,
+        reason,
+        previous_state,
+        new_state: lottery.state,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+use crate::state::governance::{Proposal, ProposalStatus};
+use crate::state::config::ProtocolConfig;
+use crate::errors::LotteryError;
+
+#[event]
+pub struct ProposalCancelled {
+    pub proposal_id: u64,
+    pub cancelled_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[derive(Accounts)]
+pub struct CancelProposal<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = config.is_admin(&admin.key()) @ LotteryError::Unauthorized
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"proposal", proposal.id.to_le_bytes().as_ref()],
+        bump = proposal.bump,
+        constraint = proposal.is_queued() @ LotteryError::ProposalNotQueued
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    pub clock: Sysvar<'info, Clock>,
+}
+
+pub fn handler(ctx: Context<CancelProposal>) -> Result<()> {
+    let proposal = &mut ctx.accounts.proposal;
+    proposal.status = ProposalStatus::Cancelled;
+
+    emit!(ProposalCancelled {
+        proposal_id: proposal.id,
+        cancelled_by: ctx.accounts.admin.key(),
+        timestamp: ctx.accounts.clock.unix_timestamp,
+    });
+
+    Ok(())
+}
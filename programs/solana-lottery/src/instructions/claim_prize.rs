@@ -1,7 +1,11 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use anchor_spl::token::{Token, TokenAccount};
 use crate::state::lottery::{Lottery, LotteryState};
+use crate::state::ticket::Ticket;
+use crate::state::claim_receipt::ClaimReceipt;
+use crate::state::config::ProtocolConfig;
 use crate::errors::LotteryError;
+use crate::vault;
 use crate::utils;
 
 #[event]
@@ -11,6 +15,8 @@ pub struct PrizeClaimed {
     pub amount: u64,
     pub timestamp: i64,
     pub matching_digits: u8,
+    pub bonus_matched: bool,
+    pub ticket_index: u64,
 }
 
 #[derive(Accounts)]
@@ -18,26 +24,61 @@ pub struct ClaimPrize<'info> {
     #[account(mut)]
     pub winner: Signer<'info>,
 
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused.claims @ LotteryError::ProtocolPaused
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+
     #[account(
         mut,
-        seeds = [b"lottery", lottery.lottery_type.discriminant().to_le_bytes().as_ref()],
+        seeds = [b"lottery", lottery.authority.as_ref(), lottery.id.to_le_bytes().as_ref()],
         bump = lottery.bump,
-        constraint = !lottery.prize_claimed @ LotteryError::PrizeAlreadyClaimed,
         constraint = lottery.state == LotteryState::Completed @ LotteryError::InvalidLotteryState,
+        constraint = !utils::is_registration_open(lottery.timing.last_draw_timestamp)? @ LotteryError::RegistrationOpen,
         constraint = !utils::is_claim_window_expired(lottery.timing.last_draw_timestamp)? @ LotteryError::ClaimWindowExpired
     )]
     pub lottery: Account<'info, Lottery>,
 
+    #[account(
+        seeds = [
+            b"ticket",
+            lottery.key().as_ref(),
+            ticket.round.to_le_bytes().as_ref(),
+            ticket.index.to_le_bytes().as_ref()
+        ],
+        bump = ticket.bump,
+        constraint = ticket.owner == winner.key() @ LotteryError::Unauthorized,
+        constraint = ticket.round == lottery.id @ LotteryError::InvalidTicket
+    )]
+    pub ticket: Account<'info, Ticket>,
+
+    // Fails to init if this ticket was already paid
+    #[account(
+        init,
+        payer = winner,
+        space = ClaimReceipt::SPACE,
+        seeds = [
+            b"claim_receipt",
+            lottery.key().as_ref(),
+            ticket.round.to_le_bytes().as_ref(),
+            ticket.index.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub claim_receipt: Account<'info, ClaimReceipt>,
+
     #[account(
         mut,
         constraint = winner_token_account.owner == winner.key(),
-        constraint = winner_token_account.mint == lottery_token_account.mint
+        constraint = winner_token_account.mint == lottery.config.mint @ LotteryError::InvalidMint
     )]
     pub winner_token_account: Account<'info, TokenAccount>,
 
     #[account(
         mut,
-        constraint = lottery_token_account.owner == lottery.key()
+        address = lottery.config.vault @ LotteryError::InvalidVault
     )]
     pub lottery_token_account: Account<'info, TokenAccount>,
 
@@ -46,37 +87,49 @@ pub struct ClaimPrize<'info> {
     pub clock: Sysvar<'info, Clock>,
 }
 
-pub fn handler(ctx: Context<ClaimPrize>, user_numbers: [u8; 6]) -> Result<()> {
+pub fn handler(ctx: Context<ClaimPrize>) -> Result<()> {
     let clock = &ctx.accounts.clock;
     let winner_key = ctx.accounts.winner.key();
-    
-    // Count matching digits and calculate prize before mutating lottery
-    let (matching_digits, prize_amount) = {
+    let ticket = &ctx.accounts.ticket;
+
+    // Split the tier's total evenly among every ticket registered for it
+    let (tier_index, matching_digits, bonus_matched, prize_amount) = {
         let lottery = &ctx.accounts.lottery;
-        let matching_digits = utils::count_matching_digits(&user_numbers, &lottery.winning_numbers);
-        require!(matching_digits >= 3, LotteryError::NotWinner);
-        
-        let prize_amount = utils::calculate_prize_amount(matching_digits, lottery.state_data.prize_amount)?;
-        (matching_digits, prize_amount)
+        let tier_index = ticket.prize_tier.ok_or(LotteryError::TicketNotRegistered)? as usize;
+        let (matching_digits, bonus_matched, _) = utils::evaluate_ticket(lottery, ticket);
+
+        let prize_amount = utils::tier_share(lottery, tier_index)?;
+        (tier_index, matching_digits, bonus_matched, prize_amount)
     };
     
     // Transfer prize to winner
-    token::transfer(
-        CpiContext::new(
-            ctx.accounts.token_program.to_account_info(),
-            Transfer {
-                from: ctx.accounts.lottery_token_account.to_account_info(),
-                to: ctx.accounts.winner_token_account.to_account_info(),
-                authority: ctx.accounts.lottery.to_account_info(),
-            },
-        ),
-        prize_amount
+    vault::transfer_from_vault(
+        &ctx.accounts.lottery,
+        &ctx.accounts.lottery_token_account,
+        ctx.accounts.winner_token_account.to_account_info(),
+        &ctx.accounts.token_program,
+        prize_amount,
     )?;
     
-    // Update lottery state after transfer
+    // Record the claim after transfer
+    let ticket_index = ctx.accounts.ticket.index;
+    let receipt = &mut ctx.accounts.claim_receipt;
+    receipt.lottery = ctx.accounts.lottery.key();
+    receipt.round = ctx.accounts.ticket.round;
+    receipt.ticket_index = ticket_index;
+    receipt.owner = winner_key;
+    receipt.tier = tier_index as u8;
+    receipt.amount = prize_amount;
+    receipt.claimed_at = clock.unix_timestamp;
+    receipt.bump = ctx.bumps.claim_receipt;
+
     let lottery = &mut ctx.accounts.lottery;
-    lottery.winner = Some(winner_key);
-    lottery.prize_claimed = true;
+    lottery.state_data.total_claimed = lottery.state_data.total_claimed
+        .checked_add(prize_amount)
+        .ok_or(LotteryError::ArithmeticError)?;
+    lottery.state_data.claimed_tickets = lottery.state_data.claimed_tickets
+        .checked_add(1)
+        .ok_or(LotteryError::ArithmeticError)?;
     
     // Emit claim event
     emit!(PrizeClaimed {
@@ -85,6 +138,8 @@ pub fn handler(ctx: Context<ClaimPrize>, user_numbers: [u8; 6]) -> Result<()> {
         amount: prize_amount,
         timestamp: clock.unix_timestamp,
         matching_digits,
+        bonus_matched,
+        ticket_index,
     });
     
     Ok(())
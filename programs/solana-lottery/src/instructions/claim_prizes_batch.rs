@@ -0,0 +1,292 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Allocate, Assign, CreateAccount, Transfer as SystemTransfer};
+use anchor_spl::token::{Token, TokenAccount};
+use crate::state::lottery::{Lottery, LotteryState};
+use crate::state::ticket::Ticket;
+use crate::state::claim_receipt::ClaimReceipt;
+use crate::state::config::ProtocolConfig;
+use crate::errors::LotteryError;
+use crate::vault;
+use crate::utils;
+
+// Each ticket costs a ticket address check, a receipt PDA search, up to three
+// system-program CPIs to create the receipt and two accounts in the
+// transaction. That is budgeted at 25k compute units per ticket in the worst
+// case, so four tickets plus the single token transfer stay under half of
+// the default 200k per-instruction budget. Raise this only with a measured
+// `computeUnitsConsumed` for a full batch.
+pub const MAX_BATCH_CLAIMS: usize = 4;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct TicketClaim {
+    pub ticket_index: u64,
+    pub tier: u8,
+    pub matching_digits: u8,
+    pub bonus_matched: bool,
+    pub amount: u64,
+}
+
+#[event]
+pub struct PrizesClaimedBatch {
+    pub lottery_id: u64,
+    pub winner: Pubkey,
+    pub total_amount: u64,
+    pub timestamp: i64,
+    pub claims: Vec<TicketClaim>,
+}
+
+// Remaining accounts: (ticket, claim_receipt) pairs, one per ticket claimed.
+// Receipts are created here exactly as `claim_prize` would, so a ticket can
+// be paid through either path but never both.
+#[derive(Accounts)]
+pub struct ClaimPrizesBatch<'info> {
+    #[account(mut)]
+    pub winner: Signer<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused.claims @ LotteryError::ProtocolPaused
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"lottery", lottery.authority.as_ref(), lottery.id.to_le_bytes().as_ref()],
+        bump = lottery.bump,
+        constraint = lottery.state == LotteryState::Completed @ LotteryError::InvalidLotteryState,
+        constraint = !utils::is_registration_open(lottery.timing.last_draw_timestamp)? @ LotteryError::RegistrationOpen,
+        constraint = !utils::is_claim_window_expired(lottery.timing.last_draw_timestamp)? @ LotteryError::ClaimWindowExpired
+    )]
+    pub lottery: Account<'info, Lottery>,
+
+    #[account(
+        mut,
+        constraint = winner_token_account.owner == winner.key(),
+        constraint = winner_token_account.mint == lottery.config.mint @ LotteryError::InvalidMint
+    )]
+    pub winner_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        address = lottery.config.vault @ LotteryError::InvalidVault
+    )]
+    pub lottery_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub clock: Sysvar<'info, Clock>,
+}
+
+// Same as Anchor's `init`, including the case where the address was pre-funded.
+fn create_receipt_account<'info>(
+    payer: &AccountInfo<'info>,
+    receipt: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    seeds: &[&[u8]],
+    rent: &Rent,
+) -> Result<()> {
+    let required = rent.minimum_balance(ClaimReceipt::SPACE);
+    let current = receipt.lamports();
+
+    if current == 0 {
+        return system_program::create_account(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                CreateAccount { from: payer.clone(), to: receipt.clone() },
+                &[seeds],
+            ),
+            required,
+            ClaimReceipt::SPACE as u64,
+            &crate::ID,
+        );
+    }
+
+    let top_up = required.saturating_sub(current);
+    if top_up > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.clone(),
+                SystemTransfer { from: payer.clone(), to: receipt.clone() },
+            ),
+            top_up,
+        )?;
+    }
+    system_program::allocate(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            Allocate { account_to_allocate: receipt.clone() },
+            &[seeds],
+        ),
+        ClaimReceipt::SPACE as u64,
+    )?;
+    system_program::assign(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            Assign { account_to_assign: receipt.clone() },
+            &[seeds],
+        ),
+        &crate::ID,
+    )
+}
+
+/// Loads a ticket passed through `remaining_accounts` and applies the seed
+/// and round checks `ClaimPrize` gets from its account constraints.
+pub(crate) fn load_ticket<'info>(
+    info: &'info AccountInfo<'info>,
+    lottery: &Account<'info, Lottery>,
+) -> Result<Account<'info, Ticket>> {
+    let ticket = Account::<Ticket>::try_from(info)?;
+    let lottery_key = lottery.key();
+    let ticket_key = Pubkey::create_program_address(
+        &[
+            b"ticket",
+            lottery_key.as_ref(),
+            ticket.round.to_le_bytes().as_ref(),
+            ticket.index.to_le_bytes().as_ref(),
+            &[ticket.bump],
+        ],
+        &crate::ID,
+    ).map_err(|_| LotteryError::InvalidTicket)?;
+    require_keys_eq!(ticket_key, info.key(), LotteryError::InvalidTicket);
+    require!(ticket.round == lottery.id, LotteryError::InvalidTicket);
+    Ok(ticket)
+}
+
+/// Address and bump of the ticket's `ClaimReceipt` PDA.
+pub(crate) fn claim_receipt_address(lottery_key: &Pubkey, ticket: &Ticket) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            b"claim_receipt",
+            lottery_key.as_ref(),
+            ticket.round.to_le_bytes().as_ref(),
+            ticket.index.to_le_bytes().as_ref(),
+        ],
+        &crate::ID,
+    )
+}
+
+/// Creates and fills the ticket's `ClaimReceipt` at `receipt_info`. Fails
+/// with `PrizeAlreadyClaimed` if the receipt already exists.
+pub(crate) fn init_claim_receipt<'info>(
+    payer: &AccountInfo<'info>,
+    receipt_info: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    lottery_key: Pubkey,
+    ticket: &Ticket,
+    tier: u8,
+    amount: u64,
+) -> Result<()> {
+    let round_bytes = ticket.round.to_le_bytes();
+    let index_bytes = ticket.index.to_le_bytes();
+    let (receipt_key, bump) = claim_receipt_address(&lottery_key, ticket);
+    require_keys_eq!(receipt_key, receipt_info.key(), LotteryError::InvalidTicket);
+    require!(
+        receipt_info.data_is_empty() && receipt_info.owner == &System::id(),
+        LotteryError::PrizeAlreadyClaimed
+    );
+
+    let seeds: &[&[u8]] = &[
+        b"claim_receipt",
+        lottery_key.as_ref(),
+        round_bytes.as_ref(),
+        index_bytes.as_ref(),
+        &[bump],
+    ];
+    create_receipt_account(payer, receipt_info, system_program, seeds, &Rent::get()?)?;
+
+    let receipt = ClaimReceipt {
+        lottery: lottery_key,
+        round: ticket.round,
+        ticket_index: ticket.index,
+        owner: ticket.owner,
+        tier,
+        amount,
+        claimed_at: Clock::get()?.unix_timestamp,
+        bump,
+    };
+    let mut data = receipt_info.try_borrow_mut_data()?;
+    receipt.try_serialize(&mut &mut data[..])?;
+    Ok(())
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, ClaimPrizesBatch<'info>>) -> Result<()> {
+    let remaining = ctx.remaining_accounts;
+    require!(
+        !remaining.is_empty() && remaining.len() % 2 == 0 && remaining.len() / 2 <= MAX_BATCH_CLAIMS,
+        LotteryError::InvalidBatchSize
+    );
+
+    let clock = &ctx.accounts.clock;
+    let winner_key = ctx.accounts.winner.key();
+    let lottery_key = ctx.accounts.lottery.key();
+    let payer = ctx.accounts.winner.to_account_info();
+    let system_program = ctx.accounts.system_program.to_account_info();
+
+    let mut claims = Vec::with_capacity(remaining.len() / 2);
+    let mut total_amount: u64 = 0;
+
+    {
+        let lottery = &ctx.accounts.lottery;
+
+        for pair in remaining.chunks_exact(2) {
+            let (ticket_info, receipt_info) = (&pair[0], &pair[1]);
+
+            let ticket = load_ticket(ticket_info, lottery)?;
+            require_keys_eq!(ticket.owner, winner_key, LotteryError::Unauthorized);
+
+            let tier_index = ticket.prize_tier.ok_or(LotteryError::TicketNotRegistered)? as usize;
+            let (matching_digits, bonus_matched, _) = utils::evaluate_ticket(lottery, &ticket);
+            let amount = utils::tier_share(lottery, tier_index)?;
+
+            init_claim_receipt(
+                &payer,
+                receipt_info,
+                &system_program,
+                lottery_key,
+                &ticket,
+                tier_index as u8,
+                amount,
+            )?;
+
+            total_amount = total_amount
+                .checked_add(amount)
+                .ok_or(LotteryError::ArithmeticError)?;
+            claims.push(TicketClaim {
+                ticket_index: ticket.index,
+                tier: tier_index as u8,
+                matching_digits,
+                bonus_matched,
+                amount,
+            });
+        }
+    }
+
+    // One transfer for the whole batch
+    let lottery = &ctx.accounts.lottery;
+    vault::transfer_from_vault(
+        lottery,
+        &ctx.accounts.lottery_token_account,
+        ctx.accounts.winner_token_account.to_account_info(),
+        &ctx.accounts.token_program,
+        total_amount,
+    )?;
+
+    let lottery = &mut ctx.accounts.lottery;
+    lottery.state_data.total_claimed = lottery.state_data.total_claimed
+        .checked_add(total_amount)
+        .ok_or(LotteryError::ArithmeticError)?;
+    lottery.state_data.claimed_tickets = lottery.state_data.claimed_tickets
+        .checked_add(claims.len() as u32)
+        .ok_or(LotteryError::ArithmeticError)?;
+
+    emit!(PrizesClaimedBatch {
+        lottery_id: lottery.id,
+        winner: winner_key,
+        total_amount,
+        timestamp: clock.unix_timestamp,
+        claims,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,78 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount};
+use crate::state::lottery::{Lottery, LotteryState};
+use crate::state::ticket::Ticket;
+use crate::errors::LotteryError;
+use crate::vault;
+use crate::instructions::claim_bond_refund::TicketRefunded;
+
+#[derive(Accounts)]
+pub struct ClaimRefund<'info> {
+    pub owner: Signer<'info>,
+
+    #[account(
+        seeds = [b"lottery", lottery.authority.as_ref(), lottery.id.to_le_bytes().as_ref()],
+        bump = lottery.bump,
+        constraint = lottery.state == LotteryState::Refunding @ LotteryError::InvalidLotteryState,
+        constraint = Clock::get()?.unix_timestamp <= lottery.timing.refund_deadline @ LotteryError::RefundWindowExpired
+    )]
+    pub lottery: Account<'info, Lottery>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"ticket",
+            lottery.key().as_ref(),
+            ticket.round.to_le_bytes().as_ref(),
+            ticket.index.to_le_bytes().as_ref()
+        ],
+        bump = ticket.bump,
+        constraint = ticket.owner == owner.key() @ LotteryError::Unauthorized,
+        constraint = ticket.round == lottery.id @ LotteryError::InvalidTicket,
+        constraint = !ticket.refunded @ LotteryError::TicketAlreadyRefunded
+    )]
+    pub ticket: Account<'info, Ticket>,
+
+    #[account(
+        mut,
+        constraint = owner_token_account.owner == owner.key(),
+        constraint = owner_token_account.mint == lottery.config.mint @ LotteryError::InvalidMint
+    )]
+    pub owner_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        address = lottery.config.vault @ LotteryError::InvalidVault
+    )]
+    pub lottery_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub clock: Sysvar<'info, Clock>,
+}
+
+pub fn handler(ctx: Context<ClaimRefund>) -> Result<()> {
+    let lottery = &ctx.accounts.lottery;
+    let clock = &ctx.accounts.clock;
+    let amount = lottery.config.ticket_price;
+
+    vault::transfer_from_vault(
+        lottery,
+        &ctx.accounts.lottery_token_account,
+        ctx.accounts.owner_token_account.to_account_info(),
+        &ctx.accounts.token_program,
+        amount,
+    )?;
+
+    let ticket = &mut ctx.accounts.ticket;
+    ticket.refunded = true;
+
+    emit!(TicketRefunded {
+        lottery_id: lottery.id,
+        ticket_index: ticket.index,
+        owner: ticket.owner,
+        amount,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
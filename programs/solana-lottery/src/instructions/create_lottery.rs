@@ -1,61 +1,218 @@
 use anchor_lang::prelude::*;
-use crate::state::lottery::{Lottery, LotteryState, LotteryType, LotteryConfig, LotteryStateData, LotteryTiming};
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+use pyth_sdk_solana::state::load_price_account;
+use crate::state::lottery::{
+    Lottery, LotteryState, LotteryType, LotteryConfig, LotteryStateData, LotteryTiming, MatchMode,
+    RandomnessSource,
+};
+use crate::state::series::LotterySeries;
+use crate::state::prize_table::{PrizeTable, MAX_PRIZE_TIERS};
+use crate::state::treasury::Treasury;
+use crate::state::role::{Role, RoleAssignment};
+use crate::state::config::ProtocolConfig;
+use crate::numbers::{BonusDomain, NumberDomain};
+use crate::errors::LotteryError;
+use crate::vault::VAULT_SEED;
 use crate::utils;
 
+#[event]
+pub struct LotteryCreated {
+    pub lottery_id: u64,
+    pub lottery_type: LotteryType,
+    pub authority: Pubkey,
+    pub start_time: i64,
+    pub end_time: i64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CreateLotteryParams {
+    pub lottery_type: LotteryType,
+    pub start_time: Option<i64>,
+    pub max_price_age: Option<u64>,
+    pub number_domain: Option<NumberDomain>,
+    pub match_mode: MatchMode,
+    pub bonus_domain: Option<BonusDomain>,
+    pub randomness: RandomnessSource,
+    pub vrf_oracle: Option<Pubkey>,
+    pub bond_amount: u64,
+}
+
 #[derive(Accounts)]
-#[instruction(lottery_type: LotteryType)]
 pub struct CreateLottery<'info> {
     #[account(mut)]
     pub admin: Signer<'info>,
 
+    #[account(constraint = operator_role.grants(Role::Operator, &admin.key()) @ LotteryError::Unauthorized)]
+    pub operator_role: Account<'info, RoleAssignment>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused.lottery_creation @ LotteryError::ProtocolPaused
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"series", admin.key().as_ref()],
+        bump = series.bump,
+        constraint = series.authority == admin.key() @ LotteryError::Unauthorized
+    )]
+    pub series: Account<'info, LotterySeries>,
+
     #[account(
         init,
         payer = admin,
         space = Lottery::SPACE,
-        seeds = [b"lottery", lottery_type.discriminant().to_le_bytes().as_ref()],
+        seeds = [b"lottery", admin.key().as_ref(), series.next_round.to_le_bytes().as_ref()],
         bump
     )]
     pub lottery: Account<'info, Lottery>,
 
-    /// CHECK: Validated in handler
+    /// CHECK: Parsed as a Pyth price account in handler
     pub pyth_price_feed: AccountInfo<'info>,
 
+    /// Fees from this round are paid into the treasury vault, so it must exist
+    #[account(
+        seeds = [b"treasury"],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    #[account(address = treasury.token_account @ LotteryError::InvalidVault)]
+    pub treasury_token_account: Account<'info, TokenAccount>,
+
+    /// Tiers are copied into the lottery; the built-in table is used if absent
+    pub prize_table: Option<Account<'info, PrizeTable>>,
+
+    /// Must be on the protocol's allowed list, priced in the same units as
+    /// tickets, and the treasury vault's mint so fees and leftovers can move
+    #[account(
+        constraint = config.is_mint_allowed(&mint.key()) @ LotteryError::MintNotAllowed,
+        constraint = mint.key() == treasury_token_account.mint @ LotteryError::InvalidMint,
+        constraint = mint.decimals == utils::PRICE_DECIMALS @ LotteryError::InvalidMintDecimals
+    )]
+    pub mint: Account<'info, Mint>,
+
+    /// Lottery vault, owned by the lottery PDA; holds ticket sales and any bond
+    #[account(
+        init,
+        payer = admin,
+        seeds = [VAULT_SEED, lottery.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = lottery
+    )]
+    pub lottery_token_account: Account<'info, TokenAccount>,
+
+    /// Source of the operator bond; required for commit-reveal lotteries
+    #[account(
+        mut,
+        constraint = admin_token_account.owner == admin.key() @ LotteryError::InvalidTokenAccount,
+        constraint = admin_token_account.mint == mint.key() @ LotteryError::InvalidMint
+    )]
+    pub admin_token_account: Option<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
     pub clock: Sysvar<'info, Clock>,
 }
 
-impl LotteryType {
-    pub fn discriminant(&self) -> u64 {
-        match self {
-            LotteryType::Daily => 0,
-            LotteryType::Weekly => 1,
-            LotteryType::Monthly => 2,
-        }
-    }
-}
-
-pub fn handler(ctx: Context<CreateLottery>, lottery_type: LotteryType) -> Result<()> {
+pub fn handler(ctx: Context<CreateLottery>, params: CreateLotteryParams) -> Result<()> {
     let lottery = &mut ctx.accounts.lottery;
     let clock = &ctx.accounts.clock;
+    let lottery_type = params.lottery_type;
+    
+    // Rounds may be pre-created to open at a later time, but never in the past
+    let start_time = params.start_time.unwrap_or(clock.unix_timestamp);
+    require!(start_time >= clock.unix_timestamp, LotteryError::InvalidTimeRange);
+    
+    // Only feeds from the protocol oracle registry, and only real Pyth accounts
+    let max_price_age = params.max_price_age.unwrap_or(utils::DEFAULT_MAX_PRICE_AGE);
+    require!(max_price_age > 0, LotteryError::InvalidMaxPriceAge);
+    require!(
+        ctx.accounts.config.is_oracle_registered(&ctx.accounts.pyth_price_feed.key()),
+        LotteryError::OracleNotRegistered
+    );
+    {
+        let data = ctx.accounts.pyth_price_feed.try_borrow_data()?;
+        load_price_account::<32, ()>(&data).map_err(|_| LotteryError::InvalidPythFeed)?;
+    }
+    
+    let number_domain = params.number_domain.unwrap_or_default();
+    number_domain.validate()?;
+    
+    // Set intersection only makes sense when a ticket cannot repeat a number
+    require!(
+        params.match_mode != MatchMode::Unordered || !number_domain.allow_repeats,
+        LotteryError::InvalidNumberDomain
+    );
+    if let Some(bonus_domain) = &params.bonus_domain {
+        bonus_domain.validate()?;
+    }
+    
+    let has_bonus = params.bonus_domain.is_some();
+    let prize_tiers = match &ctx.accounts.prize_table {
+        Some(table) => {
+            table.tiers.validate_for(number_domain.picks, has_bonus)?;
+            table.tiers.clone()
+        }
+        None => utils::default_prize_tiers(number_domain.picks, has_bonus)?,
+    };
+    
+    // VRF draws need a registered oracle key to verify proofs against
+    let vrf_oracle = params.vrf_oracle.unwrap_or_default();
+    require!(
+        params.randomness != RandomnessSource::Vrf || vrf_oracle != Pubkey::default(),
+        LotteryError::InvalidRandomnessSource
+    );
+    if params.randomness == RandomnessSource::Vrf {
+        require!(
+            ctx.accounts.config.is_oracle_registered(&vrf_oracle),
+            LotteryError::OracleNotRegistered
+        );
+    }
+    
+    // Commit-reveal operators post a bond that is forfeited if they never reveal
+    let bond_amount = match params.randomness {
+        RandomnessSource::CommitReveal => {
+            require!(params.bond_amount > 0, LotteryError::InvalidBond);
+            params.bond_amount
+        }
+        _ => 0,
+    };
     
     // Set basic lottery info
-    lottery.id = clock.unix_timestamp as u64;
+    lottery.id = ctx.accounts.series.take_round()?;
     lottery.lottery_type = lottery_type;
     lottery.state = LotteryState::Created;
     lottery.bump = ctx.bumps.lottery;
+    lottery.authority = ctx.accounts.admin.key();
     
     // Initialize config
     lottery.config = LotteryConfig {
         ticket_price: lottery.get_ticket_price(),
         min_pool_amount: lottery.get_min_pool_amount(),
         pyth_price_account: ctx.accounts.pyth_price_feed.key(),
+        max_price_age,
+        number_domain,
+        match_mode: params.match_mode,
+        bonus_domain: params.bonus_domain,
+        prize_tiers,
+        randomness: params.randomness,
+        vrf_oracle,
+        bond_amount,
+        fee_bps: ctx.accounts.config.fee_bps,
+        mint: ctx.accounts.mint.key(),
+        vault: ctx.accounts.lottery_token_account.key(),
     };
     
     // Initialize timing
     lottery.timing = LotteryTiming {
-        start_time: clock.unix_timestamp,
-        end_time: clock.unix_timestamp + lottery.get_duration(),
+        start_time,
+        end_time: start_time + lottery.get_duration(),
         last_draw_timestamp: 0,
+        refund_deadline: 0,
     };
     
     // Initialize state data
@@ -64,10 +221,41 @@ pub fn handler(ctx: Context<CreateLottery>, lottery_type: LotteryType) -> Result
         current_pool_amount: 0,
         prize_amount: 0,
         treasury_fee: 0,
+        tier_winners: [0; MAX_PRIZE_TIERS],
+        total_claimed: 0,
+        claimed_tickets: 0,
+        payout_cursor: 0,
     };
     
     // Validate lottery parameters
     utils::validate_lottery_type(lottery_type, lottery.config.ticket_price)?;
+    lottery.config.prize_tiers.validate_for_pool(lottery.min_prize_pool()?)?;
+    
+    if bond_amount > 0 {
+        let from = ctx.accounts.admin_token_account
+            .as_ref()
+            .ok_or(LotteryError::InvalidBond)?;
+        
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: from.to_account_info(),
+                    to: ctx.accounts.lottery_token_account.to_account_info(),
+                    authority: ctx.accounts.admin.to_account_info(),
+                },
+            ),
+            bond_amount
+        )?;
+    }
+    
+    emit!(LotteryCreated {
+        lottery_id: lottery.id,
+        lottery_type,
+        authority: lottery.authority,
+        start_time: lottery.timing.start_time,
+        end_time: lottery.timing.end_time,
+    });
     
     Ok(())
 }
\ No newline at end of file
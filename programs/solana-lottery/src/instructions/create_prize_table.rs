@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+use crate::state::prize_table::{PrizeTable, PrizeTier, PrizeTiers};
+
+#[event]
+pub struct PrizeTableCreated {
+    pub authority: Pubkey,
+    pub table_id: u64,
+    pub tier_count: u8,
+}
+
+#[derive(Accounts)]
+#[instruction(table_id: u64)]
+pub struct CreatePrizeTable<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = PrizeTable::SPACE,
+        seeds = [b"prize_table", authority.key().as_ref(), table_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub prize_table: Account<'info, PrizeTable>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<CreatePrizeTable>, table_id: u64, tiers: Vec<PrizeTier>) -> Result<()> {
+    let tiers = PrizeTiers::from_slice(&tiers)?;
+    tiers.validate()?;
+
+    let prize_table = &mut ctx.accounts.prize_table;
+    prize_table.authority = ctx.accounts.authority.key();
+    prize_table.table_id = table_id;
+    prize_table.tiers = tiers;
+    prize_table.bump = ctx.bumps.prize_table;
+
+    emit!(PrizeTableCreated {
+        authority: prize_table.authority,
+        table_id,
+        tier_count: prize_table.tiers.count,
+    });
+
+    Ok(())
+}
@@ -1,8 +1,10 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use anchor_spl::token::{Token, TokenAccount};
 use crate::state::lottery::{Lottery, LotteryState};
 use crate::state::treasury::Treasury;
 use crate::errors::LotteryError;
+use crate::vault;
+use crate::utils;
 
 #[event]
 pub struct PrizeDistributed {
@@ -16,10 +18,11 @@ pub struct PrizeDistributed {
 pub struct DistributePrize<'info> {
     #[account(
         mut,
-        seeds = [b"lottery", lottery.lottery_type.discriminant().to_le_bytes().as_ref()],
+        seeds = [b"lottery", lottery.authority.as_ref(), lottery.id.to_le_bytes().as_ref()],
         bump = lottery.bump,
         constraint = lottery.state == LotteryState::Completed @ LotteryError::InvalidLotteryState,
-        constraint = lottery.prize_claimed @ LotteryError::InvalidPrizeClaim
+        constraint = !utils::is_registration_open(lottery.timing.last_draw_timestamp)? @ LotteryError::RegistrationOpen,
+        constraint = lottery.state_data.claimed_tickets as u64 == lottery.registered_winners() @ LotteryError::InvalidPrizeClaim
     )]
     pub lottery: Account<'info, Lottery>,
     
@@ -32,13 +35,14 @@ pub struct DistributePrize<'info> {
     
     #[account(
         mut,
-        constraint = lottery_token_account.owner == lottery.key()
+        address = lottery.config.vault @ LotteryError::InvalidVault
     )]
     pub lottery_token_account: Account<'info, TokenAccount>,
     
     #[account(
         mut,
-        constraint = treasury_token_account.owner == treasury.key()
+        address = treasury.token_account @ LotteryError::InvalidVault,
+        constraint = treasury_token_account.mint == lottery.config.mint @ LotteryError::InvalidMint
     )]
     pub treasury_token_account: Account<'info, TokenAccount>,
     
@@ -50,36 +54,32 @@ pub struct DistributePrize<'info> {
 pub fn handler(ctx: Context<DistributePrize>) -> Result<()> {
     let clock = &ctx.accounts.clock;
     
-    // Calculate remaining amount before mutating lottery
-    let remaining_amount = {
-        let lottery = &ctx.accounts.lottery;
-        lottery.state_data.current_pool_amount
-            .checked_sub(lottery.state_data.prize_amount)
-            .ok_or(LotteryError::ArithmeticError)?
-    };
+    // Every registered winner has been paid; whatever is left goes to the treasury
+    let remaining_amount = ctx.accounts.lottery.unclaimed_amount()?;
     
     // Transfer to treasury
-    token::transfer(
-        CpiContext::new(
-            ctx.accounts.token_program.to_account_info(),
-            Transfer {
-                from: ctx.accounts.lottery_token_account.to_account_info(),
-                to: ctx.accounts.treasury_token_account.to_account_info(),
-                authority: ctx.accounts.lottery.to_account_info(),
-            },
-        ),
-        remaining_amount
+    vault::transfer_from_vault(
+        &ctx.accounts.lottery,
+        &ctx.accounts.lottery_token_account,
+        ctx.accounts.treasury_token_account.to_account_info(),
+        &ctx.accounts.token_program,
+        remaining_amount,
     )?;
     
     // Update lottery state after transfer
+    // Only the fee share counts as collected fees; unclaimed prize money is just balance
+    let fee = remaining_amount.min(ctx.accounts.lottery.state_data.treasury_fee);
+    ctx.accounts.treasury.record_deposit(remaining_amount, fee)?;
+    
     let lottery = &mut ctx.accounts.lottery;
     lottery.state = LotteryState::Expired;
+    lottery.state_data.current_pool_amount = lottery.state_data.total_claimed;
     
     // Emit distribution event
     emit!(PrizeDistributed {
         lottery_id: lottery.id,
-        prize_amount: lottery.state_data.prize_amount,
-        treasury_fee: lottery.state_data.treasury_fee,
+        prize_amount: lottery.state_data.total_claimed,
+        treasury_fee: remaining_amount,
         timestamp: clock.unix_timestamp,
     });
     
@@ -1,5 +1,8 @@
 use anchor_lang::prelude::*;
-use crate::state::lottery::{Lottery, LotteryState};
+use crate::state::lottery::{Lottery, LotteryState, RandomnessSource};
+use crate::state::treasury::Treasury;
+use crate::state::role::{Role, RoleAssignment};
+use crate::state::config::ProtocolConfig;
 use crate::errors::LotteryError;
 use crate::utils;
 
@@ -7,6 +10,7 @@ use crate::utils;
 pub struct DrawExecuted {
     pub lottery_id: u64,
     pub winning_numbers: [u8; 6],
+    pub bonus_number: Option<u8>,
     pub timestamp: i64,
 }
 
@@ -14,33 +18,48 @@ pub struct DrawExecuted {
 pub struct ExecuteDraw<'info> {
     #[account(
         mut,
-        seeds = [b"lottery", lottery.lottery_type.discriminant().to_le_bytes().as_ref()],
+        seeds = [b"lottery", lottery.authority.as_ref(), lottery.id.to_le_bytes().as_ref()],
         bump = lottery.bump,
-        constraint = lottery.state == LotteryState::Drawing @ LotteryError::InvalidLotteryState
+        constraint = lottery.state == LotteryState::Drawing @ LotteryError::InvalidLotteryState,
+        constraint = lottery.config.randomness == RandomnessSource::PriceFeed @ LotteryError::InvalidRandomnessSource
     )]
     pub lottery: Account<'info, Lottery>,
 
-    /// CHECK: Validated in handler
+    pub drawer: Signer<'info>,
+
+    #[account(constraint = drawer_role.grants(Role::Drawer, &drawer.key()) @ LotteryError::Unauthorized)]
+    pub drawer_role: Account<'info, RoleAssignment>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused.draws @ LotteryError::ProtocolPaused
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+
+    /// CHECK: Must be the configured feed; parsed and freshness-checked in handler
+    #[account(address = lottery.config.pyth_price_account @ LotteryError::InvalidPythFeed)]
     pub pyth_price_feed: AccountInfo<'info>,
 
     pub clock: Sysvar<'info, Clock>,
 }
 
 // Split into smaller functions to reduce stack usage
-fn calculate_prize_distribution(total_pool: u64) -> Result<(u64, u64)> {
-    let prize_amount = (total_pool * 90) / 100;
-    let treasury_fee = total_pool.checked_sub(prize_amount)
+fn calculate_prize_distribution(total_pool: u64, fee_bps: u16) -> Result<(u64, u64)> {
+    let treasury_fee = Treasury::calculate_fee(fee_bps, total_pool)?;
+    let prize_amount = total_pool.checked_sub(treasury_fee)
         .ok_or(LotteryError::ArithmeticError)?;
     Ok((prize_amount, treasury_fee))
 }
 
-pub fn handler(ctx: Context<ExecuteDraw>) -> Result<()> {
-    let clock = &ctx.accounts.clock;
-    let lottery = &mut ctx.accounts.lottery;
-    
-    // Generate winning numbers using Pyth price feed
-    let winning_numbers = utils::generate_random_number(&ctx.accounts.pyth_price_feed)?;
+// Shared by every draw variant once the winning numbers are known
+pub(crate) fn complete_draw(
+    lottery: &mut Lottery,
+    (winning_numbers, bonus_number): ([u8; 6], Option<u8>),
+    clock: &Clock,
+) -> Result<()> {
     lottery.winning_numbers = winning_numbers;
+    lottery.bonus_number = bonus_number;
     
     // Update lottery state
     lottery.state = LotteryState::Completed;
@@ -48,7 +67,7 @@ pub fn handler(ctx: Context<ExecuteDraw>) -> Result<()> {
     
     // Calculate prize distribution
     let total_pool = lottery.state_data.current_pool_amount;
-    let (prize_amount, treasury_fee) = calculate_prize_distribution(total_pool)?;
+    let (prize_amount, treasury_fee) = calculate_prize_distribution(total_pool, lottery.config.fee_bps)?;
     lottery.state_data.prize_amount = prize_amount;
     lottery.state_data.treasury_fee = treasury_fee;
     
@@ -56,8 +75,23 @@ pub fn handler(ctx: Context<ExecuteDraw>) -> Result<()> {
     emit!(DrawExecuted {
         lottery_id: lottery.id,
         winning_numbers,
+        bonus_number,
         timestamp: clock.unix_timestamp,
     });
     
     Ok(())
+}
+
+pub fn handler(ctx: Context<ExecuteDraw>) -> Result<()> {
+    let clock = &ctx.accounts.clock;
+    let lottery = &mut ctx.accounts.lottery;
+    
+    // Generate winning numbers using Pyth price feed
+    let drawn = utils::generate_random_number(
+        &ctx.accounts.pyth_price_feed,
+        lottery.config.max_price_age,
+        &lottery.config.number_domain,
+        &lottery.config.bonus_domain,
+    )?;
+    complete_draw(lottery, drawn, clock)
 }
\ No newline at end of file
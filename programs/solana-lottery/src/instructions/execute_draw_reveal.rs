@@ -0,0 +1,99 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar;
+use anchor_spl::token::{Token, TokenAccount};
+use crate::state::lottery::{Lottery, LotteryState, RandomnessSource};
+use crate::state::role::{Role, RoleAssignment};
+use crate::state::config::ProtocolConfig;
+use crate::errors::LotteryError;
+use crate::vault;
+use crate::instructions::execute_draw::complete_draw;
+use crate::{commit_reveal, numbers};
+
+#[derive(Accounts)]
+pub struct ExecuteDrawReveal<'info> {
+    #[account(
+        mut,
+        seeds = [b"lottery", lottery.authority.as_ref(), lottery.id.to_le_bytes().as_ref()],
+        bump = lottery.bump,
+        constraint = lottery.state == LotteryState::Drawing @ LotteryError::InvalidLotteryState,
+        constraint = lottery.config.randomness == RandomnessSource::CommitReveal @ LotteryError::InvalidRandomnessSource
+    )]
+    pub lottery: Account<'info, Lottery>,
+
+    pub drawer: Signer<'info>,
+
+    #[account(constraint = drawer_role.grants(Role::Drawer, &drawer.key()) @ LotteryError::Unauthorized)]
+    pub drawer_role: Account<'info, RoleAssignment>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused.draws @ LotteryError::ProtocolPaused
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+
+    /// CHECK: Address checked; parsed in commit_reveal::find_slot_hash
+    #[account(address = sysvar::slot_hashes::ID)]
+    pub slot_hashes: UncheckedAccount<'info>,
+
+    /// Operator account the bond is returned to
+    #[account(
+        mut,
+        constraint = authority_token_account.owner == lottery.authority @ LotteryError::InvalidTokenAccount,
+        constraint = authority_token_account.mint == lottery.config.mint @ LotteryError::InvalidMint
+    )]
+    pub authority_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        address = lottery.config.vault @ LotteryError::InvalidVault
+    )]
+    pub lottery_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub clock: Sysvar<'info, Clock>,
+}
+
+pub fn handler(ctx: Context<ExecuteDrawReveal>, secret: [u8; 32]) -> Result<()> {
+    let clock = &ctx.accounts.clock;
+
+    let (slot_hash, random) = {
+        let lottery = &ctx.accounts.lottery;
+        let commitment = &lottery.draw_commitment;
+        require!(
+            commit_reveal::compute_commitment(&secret) == commitment.commitment,
+            LotteryError::InvalidReveal
+        );
+        require!(
+            clock.slot <= commitment.reveal_deadline_slot,
+            LotteryError::RevealWindowExpired
+        );
+
+        let target = commit_reveal::target_slot(commitment.commit_slot)?;
+        require!(clock.slot > target, LotteryError::RevealNotReady);
+
+        let slot_hash = commit_reveal::find_slot_hash(&ctx.accounts.slot_hashes.to_account_info(), target)?;
+        (slot_hash, commit_reveal::mix(&secret, &slot_hash, &lottery.key()))
+    };
+
+    // Return the operator bond now that the reveal happened in time
+    let lottery = &ctx.accounts.lottery;
+    vault::transfer_from_vault(
+        lottery,
+        &ctx.accounts.lottery_token_account,
+        ctx.accounts.authority_token_account.to_account_info(),
+        &ctx.accounts.token_program,
+        lottery.config.bond_amount,
+    )?;
+
+    let lottery = &mut ctx.accounts.lottery;
+    lottery.draw_commitment.secret = secret;
+    lottery.draw_commitment.slot_hash = slot_hash;
+
+    let drawn = numbers::draw_numbers(
+        &random,
+        &lottery.config.number_domain,
+        &lottery.config.bonus_domain,
+    );
+    complete_draw(lottery, drawn, clock)
+}
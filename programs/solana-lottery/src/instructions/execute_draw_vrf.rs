@@ -0,0 +1,248 @@
+use anchor_lang::prelude::*;
+use crate::state::lottery::{DrawProof, Lottery, LotteryState, RandomnessSource};
+use crate::state::role::{Role, RoleAssignment};
+use crate::state::config::ProtocolConfig;
+use crate::errors::LotteryError;
+use crate::instructions::execute_draw::complete_draw;
+use crate::{numbers, vrf};
+
+#[derive(Accounts)]
+pub struct ExecuteDrawVrf<'info> {
+    #[account(
+        mut,
+        seeds = [b"lottery", lottery.authority.as_ref(), lottery.id.to_le_bytes().as_ref()],
+        bump = lottery.bump,
+        constraint = lottery.state == LotteryState::Drawing @ LotteryError::InvalidLotteryState,
+        constraint = lottery.config.randomness == RandomnessSource::Vrf @ LotteryError::InvalidRandomnessSource
+    )]
+    pub lottery: Account<'info, Lottery>,
+
+    pub drawer: Signer<'info>,
+
+    #[account(constraint = drawer_role.grants(Role::Drawer, &drawer.key()) @ LotteryError::Unauthorized)]
+    pub drawer_role: Account<'info, RoleAssignment>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused.draws @ LotteryError::ProtocolPaused,
+        // The oracle may have been deregistered by governance since creation
+        constraint = config.is_oracle_registered(&lottery.config.vrf_oracle) @ LotteryError::OracleNotRegistered
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+
+    pub clock: Sysvar<'info, Clock>,
+}
+
+pub fn handler(ctx: Context<ExecuteDrawVrf>, proof: [u8; vrf::PROOF_SIZE]) -> Result<()> {
+    let clock = &ctx.accounts.clock;
+    let lottery = &mut ctx.accounts.lottery;
+
+    let alpha = vrf::compute_alpha(
+        &lottery.key(),
+        lottery.id,
+        lottery.state_data.total_tickets,
+        lottery.timing.end_time,
+    );
+    let output = vrf::verify_proof(&lottery.config.vrf_oracle, &alpha, &proof)?;
+
+    // Store the proof so the draw can be re-verified off-chain
+    lottery.draw_proof = DrawProof {
+        alpha,
+        proof,
+        output,
+    };
+
+    let mut seed = [0u8; 32];
+    seed.copy_from_slice(&output[..32]);
+    let drawn = numbers::draw_numbers(
+        &seed,
+        &lottery.config.number_domain,
+        &lottery.config.bonus_domain,
+    );
+    complete_draw(lottery, drawn, clock)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::solana_program::program_error::ProgramError;
+    use anchor_lang::solana_program::sysvar;
+    use anchor_lang::InstructionData;
+    use crate::state::lottery::LotteryConfig;
+    use crate::vrf::tests::TestOracle;
+
+    const LOTTERY_ID: u64 = 3;
+    const TOTAL_TICKETS: u64 = 150;
+    const END_TIME: i64 = 1_700_000_000;
+
+    // Account storage is leaked so the infos can live for `'static`
+    fn account_info(
+        key: Pubkey,
+        owner: Pubkey,
+        data: Vec<u8>,
+        is_signer: bool,
+        is_writable: bool,
+    ) -> AccountInfo<'static> {
+        AccountInfo::new(
+            Box::leak(Box::new(key)),
+            is_signer,
+            is_writable,
+            Box::leak(Box::new(1_000_000_000u64)),
+            Box::leak(data.into_boxed_slice()),
+            Box::leak(Box::new(owner)),
+            false,
+            0,
+        )
+    }
+
+    fn serialize<T: AccountSerialize>(account: &T, space: usize) -> Vec<u8> {
+        let mut data = Vec::with_capacity(space);
+        account.try_serialize(&mut data).unwrap();
+        data.resize(space, 0);
+        data
+    }
+
+    // Bincode layout of `Clock`: five 8-byte little-endian fields
+    fn clock_data(unix_timestamp: i64) -> Vec<u8> {
+        let mut data = Vec::with_capacity(40);
+        data.extend_from_slice(&1_000u64.to_le_bytes()); // slot
+        data.extend_from_slice(&0i64.to_le_bytes()); // epoch_start_timestamp
+        data.extend_from_slice(&0u64.to_le_bytes()); // epoch
+        data.extend_from_slice(&0u64.to_le_bytes()); // leader_schedule_epoch
+        data.extend_from_slice(&unix_timestamp.to_le_bytes());
+        data
+    }
+
+    fn draw_accounts(oracle: &Pubkey) -> &'static [AccountInfo<'static>] {
+        draw_accounts_with_config(oracle, |_| {})
+    }
+
+    fn draw_accounts_with_config(
+        oracle: &Pubkey,
+        update_config: impl FnOnce(&mut ProtocolConfig),
+    ) -> &'static [AccountInfo<'static>] {
+        let program_id = crate::ID;
+        let authority = Pubkey::new_unique();
+        let (lottery_key, lottery_bump) = Pubkey::find_program_address(
+            &[b"lottery", authority.as_ref(), LOTTERY_ID.to_le_bytes().as_ref()],
+            &program_id,
+        );
+        let mut lottery = Lottery {
+            id: LOTTERY_ID,
+            state: LotteryState::Drawing,
+            bump: lottery_bump,
+            authority,
+            config: LotteryConfig {
+                randomness: RandomnessSource::Vrf,
+                vrf_oracle: *oracle,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        lottery.timing.end_time = END_TIME;
+        lottery.state_data.total_tickets = TOTAL_TICKETS;
+
+        let drawer = Pubkey::new_unique();
+        let drawer_role = RoleAssignment {
+            role: Role::Drawer,
+            holder: drawer,
+            granted_by: authority,
+            granted_at: 0,
+            bump: 0,
+        };
+
+        let (config_key, config_bump) = Pubkey::find_program_address(&[b"config"], &program_id);
+        let mut config = ProtocolConfig {
+            bump: config_bump,
+            ..Default::default()
+        };
+        config.register_oracle(*oracle).unwrap();
+        update_config(&mut config);
+
+        let accounts = vec![
+            account_info(lottery_key, program_id, serialize(&lottery, Lottery::SPACE), false, true),
+            account_info(drawer, Pubkey::default(), Vec::new(), true, false),
+            account_info(
+                Pubkey::new_unique(),
+                program_id,
+                serialize(&drawer_role, RoleAssignment::SPACE),
+                false,
+                false,
+            ),
+            account_info(config_key, program_id, serialize(&config, ProtocolConfig::SPACE), false, false),
+            account_info(sysvar::clock::ID, sysvar::ID, clock_data(END_TIME + 60), false, false),
+        ];
+        Box::leak(accounts.into_boxed_slice())
+    }
+
+    fn execute(
+        accounts: &'static [AccountInfo<'static>],
+        proof: [u8; vrf::PROOF_SIZE],
+    ) -> std::result::Result<(), ProgramError> {
+        let data = crate::instruction::ExecuteDrawVrf { proof }.data();
+        crate::entry(&crate::ID, accounts, &data)
+    }
+
+    fn alpha_for(accounts: &[AccountInfo]) -> [u8; 32] {
+        vrf::compute_alpha(accounts[0].key, LOTTERY_ID, TOTAL_TICKETS, END_TIME)
+    }
+
+    fn invalid_proof() -> ProgramError {
+        Error::from(LotteryError::InvalidVrfProof).into()
+    }
+
+    #[test]
+    fn draws_with_valid_proof() {
+        let oracle = TestOracle::new(b"oracle");
+        let accounts = draw_accounts(&oracle.public_key);
+        let alpha = alpha_for(accounts);
+        let proof = oracle.prove(&alpha);
+
+        execute(accounts, proof).unwrap();
+
+        let lottery = Lottery::try_deserialize(&mut &accounts[0].data.borrow()[..]).unwrap();
+        assert!(lottery.state == LotteryState::Completed);
+        assert_eq!(lottery.draw_proof.alpha, alpha);
+        assert_eq!(lottery.draw_proof.proof, proof);
+        assert_eq!(
+            lottery.draw_proof.output,
+            vrf::verify_proof(&oracle.public_key, &alpha, &proof).unwrap()
+        );
+    }
+
+    #[test]
+    fn rejects_proof_from_wrong_oracle() {
+        let oracle = TestOracle::new(b"oracle");
+        let impostor = TestOracle::new(b"impostor");
+        let accounts = draw_accounts(&oracle.public_key);
+        let proof = impostor.prove(&alpha_for(accounts));
+
+        assert_eq!(execute(accounts, proof), Err(invalid_proof()));
+    }
+
+    #[test]
+    fn rejects_proof_for_other_alpha() {
+        let oracle = TestOracle::new(b"oracle");
+        let accounts = draw_accounts(&oracle.public_key);
+        // A proof the oracle issued for a different ticket count
+        let other_alpha = vrf::compute_alpha(accounts[0].key, LOTTERY_ID, TOTAL_TICKETS + 1, END_TIME);
+        let proof = oracle.prove(&other_alpha);
+
+        assert_eq!(execute(accounts, proof), Err(invalid_proof()));
+    }
+
+    #[test]
+    fn rejects_deregistered_oracle() {
+        let oracle = TestOracle::new(b"oracle");
+        let accounts = draw_accounts_with_config(&oracle.public_key, |config| {
+            config.deregister_oracle(&oracle.public_key).unwrap();
+        });
+        let proof = oracle.prove(&alpha_for(accounts));
+
+        assert_eq!(
+            execute(accounts, proof),
+            Err(Error::from(LotteryError::OracleNotRegistered).into())
+        );
+    }
+}
@@ -0,0 +1,97 @@
+use anchor_lang::prelude::*;
+use crate::state::governance::{ParameterChange, Proposal, ProposalStatus};
+use crate::state::lottery::Lottery;
+use crate::state::prize_table::PrizeTable;
+use crate::state::config::ProtocolConfig;
+use crate::errors::LotteryError;
+
+#[event]
+pub struct ProposalExecuted {
+    pub proposal_id: u64,
+    pub change: ParameterChange,
+    pub timestamp: i64,
+}
+
+// Permissionless once the delay has elapsed; the admin can cancel until then.
+// Only the accounts the queued change touches need to be supplied.
+#[derive(Accounts)]
+pub struct ExecuteProposal<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"proposal", proposal.id.to_le_bytes().as_ref()],
+        bump = proposal.bump,
+        constraint = proposal.is_queued() @ LotteryError::ProposalNotQueued,
+        constraint = clock.unix_timestamp >= proposal.eta @ LotteryError::ProposalNotReady
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    /// Required for `TicketPrice` and `PrizeTable` changes
+    #[account(mut)]
+    pub lottery: Option<Account<'info, Lottery>>,
+
+    /// Required for `ParameterChange::PrizeTable`
+    pub prize_table: Option<Account<'info, PrizeTable>>,
+
+    pub clock: Sysvar<'info, Clock>,
+}
+
+fn target_lottery<'a, 'info>(
+    lottery: &'a mut Option<Account<'info, Lottery>>,
+    key: &Pubkey,
+) -> Result<&'a mut Account<'info, Lottery>> {
+    let lottery = lottery.as_mut().ok_or(LotteryError::InvalidProposal)?;
+    require_keys_eq!(lottery.key(), *key, LotteryError::InvalidProposal);
+    require!(lottery.is_configurable(), LotteryError::ParametersFrozen);
+    Ok(lottery)
+}
+
+pub fn handler(mut ctx: Context<ExecuteProposal>) -> Result<()> {
+    let change = ctx.accounts.proposal.change;
+    let accounts = &mut ctx.accounts;
+
+    match change {
+        ParameterChange::FeeBps(fee_bps) => {
+            ProtocolConfig::validate_fee_bps(fee_bps)?;
+            // Existing lotteries keep the rate they were created with
+            accounts.config.fee_bps = fee_bps;
+        }
+        ParameterChange::TicketPrice { lottery, ticket_price } => {
+            let lottery = target_lottery(&mut accounts.lottery, &lottery)?;
+            lottery.config.ticket_price = ticket_price;
+        }
+        ParameterChange::PrizeTable { lottery, prize_table } => {
+            let table = accounts.prize_table.as_ref().ok_or(LotteryError::InvalidProposal)?;
+            require_keys_eq!(table.key(), prize_table, LotteryError::InvalidProposal);
+
+            let lottery = target_lottery(&mut accounts.lottery, &lottery)?;
+            let has_bonus = lottery.config.bonus_domain.is_some();
+            table.tiers.validate_for(lottery.config.number_domain.picks, has_bonus)?;
+            table.tiers.validate_for_pool(lottery.min_prize_pool()?)?;
+            lottery.config.prize_tiers = table.tiers.clone();
+        }
+        ParameterChange::RegisterOracle(oracle) => accounts.config.register_oracle(oracle)?,
+        ParameterChange::DeregisterOracle(oracle) => accounts.config.deregister_oracle(&oracle)?,
+        ParameterChange::GovernanceDelay(delay) => {
+            ProtocolConfig::validate_governance_delay(delay)?;
+            accounts.config.governance_delay = delay;
+        }
+    }
+
+    let proposal = &mut accounts.proposal;
+    proposal.status = ProposalStatus::Executed;
+
+    emit!(ProposalExecuted {
+        proposal_id: proposal.id,
+        change,
+        timestamp: accounts.clock.unix_timestamp,
+    });
+
+    Ok(())
+}
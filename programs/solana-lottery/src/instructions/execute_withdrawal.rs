@@ -0,0 +1,153 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use crate::state::treasury::{Treasury, WithdrawalKind, WithdrawalProposal};
+use crate::state::role::{Role, RoleAssignment};
+use crate::errors::LotteryError;
+use crate::utils::{get_current_timestamp, WITHDRAWAL_TIMELOCK};
+
+#[event]
+pub struct WithdrawalExecuted {
+    pub proposal_id: u64,
+    pub kind: WithdrawalKind,
+    pub destination: Pubkey,
+    pub amount: u64,
+    pub approval_count: u8,
+    pub timestamp: i64,
+}
+
+// The signer set approves; any treasurer may then carry out the withdrawal.
+#[derive(Accounts)]
+pub struct ExecuteWithdrawal<'info> {
+    pub treasurer: Signer<'info>,
+
+    #[account(constraint = treasurer_role.grants(Role::Treasurer, &treasurer.key()) @ LotteryError::Unauthorized)]
+    pub treasurer_role: Account<'info, RoleAssignment>,
+
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump = treasury.bump,
+        constraint = get_current_timestamp()? > treasury.time_locked @ LotteryError::TimelockActive
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    #[account(
+        mut,
+        seeds = [b"withdrawal", treasury.key().as_ref(), proposal.id.to_le_bytes().as_ref()],
+        bump = proposal.bump,
+        constraint = !proposal.executed @ LotteryError::ProposalExecuted,
+        constraint = proposal.approval_count >= treasury.threshold @ LotteryError::ThresholdNotMet
+    )]
+    pub proposal: Account<'info, WithdrawalProposal>,
+
+    #[account(
+        mut,
+        address = treasury.token_account @ LotteryError::InvalidVault
+    )]
+    pub treasury_token_account: Account<'info, TokenAccount>,
+
+    /// Required for `WithdrawalKind::Tokens`
+    #[account(
+        mut,
+        address = proposal.destination @ LotteryError::InvalidWithdrawal,
+        constraint = destination_token_account.mint == treasury_token_account.mint @ LotteryError::InvalidMint
+    )]
+    pub destination_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// CHECK: Plain lamport recipient; required for `WithdrawalKind::RentLamports`
+    #[account(
+        mut,
+        address = proposal.destination @ LotteryError::InvalidWithdrawal
+    )]
+    pub destination_wallet: Option<UncheckedAccount<'info>>,
+
+    pub token_program: Program<'info, Token>,
+    pub clock: Sysvar<'info, Clock>,
+}
+
+fn withdraw_tokens(ctx: &mut Context<ExecuteWithdrawal>, amount: u64) -> Result<()> {
+    let destination = ctx.accounts.destination_token_account
+        .as_ref()
+        .ok_or(LotteryError::InvalidWithdrawal)?;
+    require!(
+        amount <= ctx.accounts.treasury.balance && amount <= ctx.accounts.treasury_token_account.amount,
+        LotteryError::InsufficientTreasuryBalance
+    );
+
+    // Transfer tokens out of the vault, signed by the treasury PDA
+    let treasury_seeds: &[&[u8]] = &[b"treasury", &[ctx.accounts.treasury.bump]];
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.treasury_token_account.to_account_info(),
+                to: destination.to_account_info(),
+                authority: ctx.accounts.treasury.to_account_info(),
+            },
+            &[treasury_seeds],
+        ),
+        amount
+    )?;
+
+    // Update treasury state; emits `TreasuryWithdrawal`
+    ctx.accounts.treasury.withdraw(amount, &ctx.accounts.clock)
+}
+
+// Lamports sent straight to the treasury PDA are not part of the token
+// balance. Only the excess over its rent-exempt minimum can leave.
+fn withdraw_rent_lamports(ctx: &mut Context<ExecuteWithdrawal>, amount: u64) -> Result<()> {
+    let destination = ctx.accounts.destination_wallet
+        .as_ref()
+        .ok_or(LotteryError::InvalidWithdrawal)?
+        .to_account_info();
+
+    let treasury_info = ctx.accounts.treasury.to_account_info();
+    let rent_floor = Rent::get()?.minimum_balance(treasury_info.data_len());
+    let remaining_lamports = treasury_info
+        .lamports()
+        .checked_sub(amount)
+        .ok_or(LotteryError::InsufficientTreasuryBalance)?;
+    require!(remaining_lamports >= rent_floor, LotteryError::InsufficientTreasuryBalance);
+
+    **treasury_info.try_borrow_mut_lamports()? = remaining_lamports;
+
+    let destination_lamports = destination
+        .lamports()
+        .checked_add(amount)
+        .ok_or(LotteryError::ArithmeticError)?;
+    **destination.try_borrow_mut_lamports()? = destination_lamports;
+
+    Ok(())
+}
+
+pub fn handler(mut ctx: Context<ExecuteWithdrawal>) -> Result<()> {
+    let kind = ctx.accounts.proposal.kind;
+    let amount = ctx.accounts.proposal.amount;
+
+    match kind {
+        WithdrawalKind::Tokens => withdraw_tokens(&mut ctx, amount)?,
+        WithdrawalKind::RentLamports => withdraw_rent_lamports(&mut ctx, amount)?,
+    }
+
+    let clock = &ctx.accounts.clock;
+
+    // Update timelock for next withdrawal
+    let treasury = &mut ctx.accounts.treasury;
+    treasury.time_locked = clock.unix_timestamp
+        .checked_add(WITHDRAWAL_TIMELOCK)
+        .ok_or(LotteryError::ArithmeticError)?;
+
+    let proposal = &mut ctx.accounts.proposal;
+    proposal.executed = true;
+
+    emit!(WithdrawalExecuted {
+        proposal_id: proposal.id,
+        kind,
+        destination: proposal.destination,
+        amount,
+        approval_count: proposal.approval_count,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
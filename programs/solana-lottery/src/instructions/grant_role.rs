@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+use crate::state::role::{Role, RoleAssignment};
+use crate::state::config::ProtocolConfig;
+use crate::errors::LotteryError;
+
+#[event]
+pub struct RoleGranted {
+    pub role: Role,
+    pub holder: Pubkey,
+    pub granted_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[derive(Accounts)]
+#[instruction(role: Role, holder: Pubkey)]
+pub struct GrantRole<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = config.is_admin(&admin.key())
+            || RoleAssignment::held(&admin_role, Role::Admin, &admin.key()) @ LotteryError::Unauthorized
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+
+    /// Not needed when signing as the config admin
+    pub admin_role: Option<Account<'info, RoleAssignment>>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = RoleAssignment::SPACE,
+        seeds = [b"role", role.seed().as_ref(), holder.as_ref()],
+        bump
+    )]
+    pub assignment: Account<'info, RoleAssignment>,
+
+    pub system_program: Program<'info, System>,
+    pub clock: Sysvar<'info, Clock>,
+}
+
+pub fn handler(ctx: Context<GrantRole>, role: Role, holder: Pubkey) -> Result<()> {
+    require!(holder != Pubkey::default(), LotteryError::InvalidConfig);
+
+    let clock = &ctx.accounts.clock;
+    let assignment = &mut ctx.accounts.assignment;
+    assignment.role = role;
+    assignment.holder = holder;
+    assignment.granted_by = ctx.accounts.admin.key();
+    assignment.granted_at = clock.unix_timestamp;
+    assignment.bump = ctx.bumps.assignment;
+
+    emit!(RoleGranted {
+        role,
+        holder,
+        granted_by: assignment.granted_by,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
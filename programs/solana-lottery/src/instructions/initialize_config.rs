@@ -0,0 +1,66 @@
+use anchor_lang::prelude::*;
+use crate::state::config::{PauseFlags, ProtocolConfig};
+use crate::errors::LotteryError;
+use crate::program::SolanaLottery;
+use crate::utils::DEFAULT_GOVERNANCE_DELAY;
+
+#[event]
+pub struct ConfigInitialized {
+    pub admin: Pubkey,
+    pub fee_bps: u16,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct InitializeConfigParams {
+    pub fee_bps: u16,
+    pub allowed_mints: Vec<Pubkey>,
+    pub oracles: Vec<Pubkey>,
+    pub governance_delay: Option<i64>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeConfig<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    // Only the upgrade authority may claim the admin seat
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()) @ LotteryError::Unauthorized)]
+    pub program: Program<'info, SolanaLottery>,
+
+    #[account(constraint = program_data.upgrade_authority_address == Some(admin.key()) @ LotteryError::Unauthorized)]
+    pub program_data: Account<'info, ProgramData>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = ProtocolConfig::SPACE,
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<InitializeConfig>, params: InitializeConfigParams) -> Result<()> {
+    ProtocolConfig::validate_fee_bps(params.fee_bps)?;
+    let governance_delay = params.governance_delay.unwrap_or(DEFAULT_GOVERNANCE_DELAY);
+    ProtocolConfig::validate_governance_delay(governance_delay)?;
+
+    let config = &mut ctx.accounts.config;
+    config.admin = ctx.accounts.admin.key();
+    config.fee_bps = params.fee_bps;
+    config.set_allowed_mints(&params.allowed_mints)?;
+    config.set_oracles(&params.oracles)?;
+    config.paused = PauseFlags::default();
+    config.bump = ctx.bumps.config;
+    config.governance_delay = governance_delay;
+    config.next_proposal_id = 0;
+
+    emit!(ConfigInitialized {
+        admin: config.admin,
+        fee_bps: config.fee_bps,
+    });
+
+    Ok(())
+}
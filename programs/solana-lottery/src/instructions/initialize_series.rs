@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+use crate::state::series::LotterySeries;
+use crate::state::role::{Role, RoleAssignment};
+use crate::errors::LotteryError;
+
+#[derive(Accounts)]
+pub struct InitializeSeries<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(constraint = operator_role.grants(Role::Operator, &authority.key()) @ LotteryError::Unauthorized)]
+    pub operator_role: Account<'info, RoleAssignment>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = LotterySeries::SPACE,
+        seeds = [b"series", authority.key().as_ref()],
+        bump
+    )]
+    pub series: Account<'info, LotterySeries>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<InitializeSeries>) -> Result<()> {
+    let series = &mut ctx.accounts.series;
+    series.authority = ctx.accounts.authority.key();
+    series.next_round = 0;
+    series.bump = ctx.bumps.series;
+    Ok(())
+}
@@ -0,0 +1,90 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+use crate::state::treasury::Treasury;
+use crate::state::config::ProtocolConfig;
+use crate::errors::LotteryError;
+use crate::utils;
+
+#[event]
+pub struct TreasuryInitialized {
+    pub authority: Pubkey,
+    pub token_account: Pubkey,
+    pub mint: Pubkey,
+    pub signer_count: u8,
+    pub threshold: u8,
+    pub timestamp: i64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct InitializeTreasuryParams {
+    pub signers: Vec<Pubkey>,
+    pub threshold: u8,
+}
+
+#[derive(Accounts)]
+pub struct InitializeTreasury<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = config.is_admin(&authority.key()) @ LotteryError::Unauthorized,
+        constraint = config.is_mint_allowed(&mint.key()) @ LotteryError::MintNotAllowed
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = Treasury::SPACE,
+        seeds = [b"treasury"],
+        bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    // Fee vault, owned and signed for by the treasury PDA
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"treasury_vault", treasury.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = treasury
+    )]
+    pub treasury_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        constraint = mint.decimals == utils::PRICE_DECIMALS @ LotteryError::InvalidMintDecimals
+    )]
+    pub mint: Account<'info, Mint>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+    pub clock: Sysvar<'info, Clock>,
+}
+
+pub fn handler(ctx: Context<InitializeTreasury>, params: InitializeTreasuryParams) -> Result<()> {
+    let treasury = &mut ctx.accounts.treasury;
+    treasury.balance = 0;
+    treasury.authority = ctx.accounts.authority.key();
+    treasury.token_account = ctx.accounts.treasury_token_account.key();
+    treasury.total_fees_collected = 0;
+    treasury.last_withdrawal = 0;
+    treasury.time_locked = 0;
+    treasury.bump = ctx.bumps.treasury;
+    treasury.set_signers(&params.signers, params.threshold)?;
+    treasury.next_proposal_id = 0;
+
+    emit!(TreasuryInitialized {
+        authority: treasury.authority,
+        token_account: treasury.token_account,
+        mint: ctx.accounts.mint.key(),
+        signer_count: treasury.signer_count,
+        threshold: treasury.threshold,
+        timestamp: ctx.accounts.clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -1,9 +1,31 @@
+pub mod initialize_config;
+pub mod update_config;
+pub mod initialize_treasury;
+pub mod initialize_series;
+pub mod create_prize_table;
 pub mod create_lottery;
+pub mod open_lottery;
 pub mod buy_ticket;
 pub mod schedule_draw;
 pub mod execute_draw;
+pub mod execute_draw_vrf;
+pub mod execute_draw_reveal;
+pub mod slash_bond;
+pub mod cancel_lottery;
+pub mod claim_bond_refund;
+pub mod claim_refund;
+pub mod sweep_refunds;
 pub mod distribute_prize;
+pub mod register_winner;
 pub mod claim_prize;
+pub mod claim_prizes_batch;
+pub mod payout_winners;
 pub mod recycle_unclaimed;
-pub mod withdraw_treasury;
-
+pub mod propose_withdrawal;
+pub mod approve_withdrawal;
+pub mod execute_withdrawal;
+pub mod queue_proposal;
+pub mod cancel_proposal;
+pub mod execute_proposal;
+pub mod grant_role;
+pub mod revoke_role;
@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+use crate::state::lottery::{Lottery, LotteryState};
+use crate::state::role::{Role, RoleAssignment};
+use crate::errors::LotteryError;
+
+#[event]
+pub struct LotteryOpened {
+    pub lottery_id: u64,
+    pub start_time: i64,
+    pub end_time: i64,
+    pub timestamp: i64,
+}
+
+#[derive(Accounts)]
+pub struct OpenLottery<'info> {
+    #[account(
+        mut,
+        seeds = [b"lottery", lottery.authority.as_ref(), lottery.id.to_le_bytes().as_ref()],
+        bump = lottery.bump,
+        has_one = authority @ LotteryError::Unauthorized,
+        constraint = lottery.state == LotteryState::Created @ LotteryError::InvalidLotteryState
+    )]
+    pub lottery: Account<'info, Lottery>,
+
+    pub authority: Signer<'info>,
+
+    #[account(constraint = operator_role.grants(Role::Operator, &authority.key()) @ LotteryError::Unauthorized)]
+    pub operator_role: Account<'info, RoleAssignment>,
+
+    pub clock: Sysvar<'info, Clock>,
+}
+
+pub fn handler(ctx: Context<OpenLottery>) -> Result<()> {
+    let lottery = &mut ctx.accounts.lottery;
+    let clock = &ctx.accounts.clock;
+
+    // Scheduled rounds only open once their start time has passed
+    require!(
+        clock.unix_timestamp >= lottery.timing.start_time,
+        LotteryError::LotteryNotStarted
+    );
+    require!(
+        clock.unix_timestamp < lottery.timing.end_time,
+        LotteryError::InvalidTimeRange
+    );
+
+    lottery.state = LotteryState::Open;
+
+    emit!(LotteryOpened {
+        lottery_id: lottery.id,
+        start_time: lottery.timing.start_time,
+        end_time: lottery.timing.end_time,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
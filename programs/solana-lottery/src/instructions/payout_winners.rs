@@ -0,0 +1,240 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use crate::state::lottery::{Lottery, LotteryState};
+use crate::state::treasury::Treasury;
+use crate::state::config::ProtocolConfig;
+use crate::errors::LotteryError;
+use crate::vault;
+use crate::instructions::claim_prizes_batch::{claim_receipt_address, init_claim_receipt, load_ticket};
+use crate::utils;
+
+// Tickets walked per call. Paying a winner costs a receipt PDA search, a
+// system-program CPI, an ATA derivation and a token CPI, so a page of eight
+// stays inside the default compute budget even if every ticket won.
+pub const MAX_PAYOUT_PAGE: usize = 8;
+
+// Paid from the treasury per winner actually paid (0.001 USDC), so walking
+// past losing or already-claimed tickets earns nothing. Keepers also fund the
+// receipt rent for the winners they pay.
+pub const KEEPER_FEE_PER_PAYOUT: u64 = 1_000;
+
+#[event]
+pub struct WinnersPaidOut {
+    pub lottery_id: u64,
+    pub from_index: u64,
+    pub next_index: u64,
+    pub winners_paid: u32,
+    pub total_paid: u64,
+    pub keeper: Pubkey,
+    pub keeper_fee: u64,
+}
+
+// Remaining accounts, in ticket order starting at `state_data.payout_cursor`:
+// the ticket, followed by its claim receipt and the owner's associated token
+// account only if the ticket is registered as a winner. Tickets already paid
+// through `claim_prize`, or whose owner has no ATA yet, are skipped and stay
+// claimable through `claim_prize`.
+#[derive(Accounts)]
+pub struct PayoutWinners<'info> {
+    #[account(mut)]
+    pub keeper: Signer<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused.claims @ LotteryError::ProtocolPaused
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"lottery", lottery.authority.as_ref(), lottery.id.to_le_bytes().as_ref()],
+        bump = lottery.bump,
+        constraint = lottery.state == LotteryState::Completed @ LotteryError::InvalidLotteryState,
+        constraint = !utils::is_registration_open(lottery.timing.last_draw_timestamp)? @ LotteryError::RegistrationOpen,
+        constraint = !utils::is_claim_window_expired(lottery.timing.last_draw_timestamp)? @ LotteryError::ClaimWindowExpired,
+        constraint = lottery.state_data.payout_cursor < lottery.state_data.total_tickets @ LotteryError::PayoutsComplete
+    )]
+    pub lottery: Account<'info, Lottery>,
+
+    #[account(
+        mut,
+        address = lottery.config.vault @ LotteryError::InvalidVault
+    )]
+    pub lottery_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    #[account(
+        mut,
+        address = treasury.token_account @ LotteryError::InvalidVault,
+        constraint = treasury_token_account.mint == lottery.config.mint @ LotteryError::InvalidMint
+    )]
+    pub treasury_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = keeper_token_account.owner == keeper.key(),
+        constraint = keeper_token_account.mint == lottery.config.mint @ LotteryError::InvalidMint
+    )]
+    pub keeper_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Keeper fee for a page, limited to what the treasury holds.
+fn keeper_fee(winners_paid: u32, treasury_available: u64) -> Result<u64> {
+    let fee = (winners_paid as u64)
+        .checked_mul(KEEPER_FEE_PER_PAYOUT)
+        .ok_or(LotteryError::ArithmeticError)?;
+    Ok(fee.min(treasury_available))
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, PayoutWinners<'info>>) -> Result<()> {
+    let remaining = ctx.remaining_accounts;
+    let lottery_key = ctx.accounts.lottery.key();
+    let mint = ctx.accounts.lottery.config.mint;
+    let payer = ctx.accounts.keeper.to_account_info();
+    let system_program = ctx.accounts.system_program.to_account_info();
+
+    let from_index = ctx.accounts.lottery.state_data.payout_cursor;
+    let mut cursor = from_index;
+    let mut walked: u64 = 0;
+    let mut winners_paid: u32 = 0;
+    let mut total_paid: u64 = 0;
+    let mut accounts = remaining.iter();
+
+    {
+        let lottery = &ctx.accounts.lottery;
+        while let Some(ticket_info) = accounts.next() {
+            require!(walked < MAX_PAYOUT_PAGE as u64, LotteryError::InvalidBatchSize);
+
+            let ticket = load_ticket(ticket_info, lottery)?;
+            require!(ticket.index == cursor, LotteryError::InvalidTicket);
+
+            if let Some(tier) = ticket.prize_tier {
+                let receipt_info = accounts.next().ok_or(LotteryError::InvalidBatchSize)?;
+                let owner_info = accounts.next().ok_or(LotteryError::InvalidBatchSize)?;
+
+                require_keys_eq!(
+                    owner_info.key(),
+                    get_associated_token_address(&ticket.owner, &mint),
+                    LotteryError::InvalidTokenAccount
+                );
+
+                // An existing receipt means the owner already pulled the prize,
+                // but only if it is this ticket's receipt; owners without an
+                // ATA are left to claim it themselves
+                if !receipt_info.data_is_empty() {
+                    let (receipt_key, _) = claim_receipt_address(&lottery_key, &ticket);
+                    require_keys_eq!(receipt_info.key(), receipt_key, LotteryError::InvalidTicket);
+                } else if !owner_info.data_is_empty() {
+                    let owner_token_account = Account::<TokenAccount>::try_from(owner_info)?;
+
+                    let amount = utils::tier_share(lottery, tier as usize)?;
+                    init_claim_receipt(
+                        &payer,
+                        receipt_info,
+                        &system_program,
+                        lottery_key,
+                        &ticket,
+                        tier,
+                        amount,
+                    )?;
+
+                    vault::transfer_from_vault(
+                        lottery,
+                        &ctx.accounts.lottery_token_account,
+                        owner_token_account.to_account_info(),
+                        &ctx.accounts.token_program,
+                        amount,
+                    )?;
+
+                    total_paid = total_paid
+                        .checked_add(amount)
+                        .ok_or(LotteryError::ArithmeticError)?;
+                    winners_paid = winners_paid
+                        .checked_add(1)
+                        .ok_or(LotteryError::ArithmeticError)?;
+                }
+            }
+
+            cursor = cursor.checked_add(1).ok_or(LotteryError::ArithmeticError)?;
+            walked += 1;
+            if cursor == lottery.state_data.total_tickets {
+                break;
+            }
+        }
+    }
+    require!(walked > 0 && accounts.next().is_none(), LotteryError::InvalidBatchSize);
+
+    let keeper_fee = keeper_fee(
+        winners_paid,
+        ctx.accounts.treasury_token_account.amount.min(ctx.accounts.treasury.balance),
+    )?;
+    if keeper_fee > 0 {
+        let treasury_seeds: &[&[u8]] = &[b"treasury", &[ctx.accounts.treasury.bump]];
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.treasury_token_account.to_account_info(),
+                    to: ctx.accounts.keeper_token_account.to_account_info(),
+                    authority: ctx.accounts.treasury.to_account_info(),
+                },
+                &[treasury_seeds],
+            ),
+            keeper_fee
+        )?;
+        ctx.accounts.treasury.record_payout(keeper_fee)?;
+    }
+
+    let lottery = &mut ctx.accounts.lottery;
+    lottery.state_data.payout_cursor = cursor;
+    lottery.state_data.total_claimed = lottery.state_data.total_claimed
+        .checked_add(total_paid)
+        .ok_or(LotteryError::ArithmeticError)?;
+    lottery.state_data.claimed_tickets = lottery.state_data.claimed_tickets
+        .checked_add(winners_paid)
+        .ok_or(LotteryError::ArithmeticError)?;
+
+    emit!(WinnersPaidOut {
+        lottery_id: lottery.id,
+        from_index,
+        next_index: cursor,
+        winners_paid,
+        total_paid,
+        keeper: ctx.accounts.keeper.key(),
+        keeper_fee,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeper_fee_is_zero_for_pages_without_payouts() {
+        assert_eq!(keeper_fee(0, u64::MAX).unwrap(), 0);
+    }
+
+    #[test]
+    fn keeper_fee_scales_with_winners_paid() {
+        assert_eq!(keeper_fee(3, u64::MAX).unwrap(), 3 * KEEPER_FEE_PER_PAYOUT);
+    }
+
+    #[test]
+    fn keeper_fee_is_capped_by_treasury() {
+        assert_eq!(keeper_fee(8, KEEPER_FEE_PER_PAYOUT).unwrap(), KEEPER_FEE_PER_PAYOUT);
+        assert_eq!(keeper_fee(8, 0).unwrap(), 0);
+    }
+}
@@ -0,0 +1,78 @@
+use anchor_lang::prelude::*;
+use crate::state::treasury::{Treasury, WithdrawalKind, WithdrawalProposal};
+use crate::errors::LotteryError;
+
+#[event]
+pub struct WithdrawalProposed {
+    pub proposal_id: u64,
+    pub proposer: Pubkey,
+    pub kind: WithdrawalKind,
+    pub destination: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[derive(Accounts)]
+pub struct ProposeWithdrawal<'info> {
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump = treasury.bump,
+        constraint = treasury.is_authorized_signer(&proposer) @ LotteryError::UnauthorizedSigner
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    #[account(
+        init,
+        payer = proposer,
+        space = WithdrawalProposal::SPACE,
+        seeds = [b"withdrawal", treasury.key().as_ref(), treasury.next_proposal_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub proposal: Account<'info, WithdrawalProposal>,
+
+    pub system_program: Program<'info, System>,
+    pub clock: Sysvar<'info, Clock>,
+}
+
+pub fn handler(
+    ctx: Context<ProposeWithdrawal>,
+    kind: WithdrawalKind,
+    destination: Pubkey,
+    amount: u64,
+) -> Result<()> {
+    require!(amount > 0, LotteryError::InvalidWithdrawal);
+
+    let treasury = &mut ctx.accounts.treasury;
+    let clock = &ctx.accounts.clock;
+    let proposer = ctx.accounts.proposer.key();
+    let index = treasury.signer_index(&proposer).ok_or(LotteryError::UnauthorizedSigner)?;
+
+    let proposal = &mut ctx.accounts.proposal;
+    proposal.treasury = treasury.key();
+    proposal.id = treasury.take_proposal_id()?;
+    proposal.proposer = proposer;
+    proposal.kind = kind;
+    proposal.destination = destination;
+    proposal.amount = amount;
+    proposal.executed = false;
+    proposal.created_at = clock.unix_timestamp;
+    proposal.bump = ctx.bumps.proposal;
+
+    // Proposing counts as the proposer's approval
+    proposal.approve(index)?;
+
+    emit!(WithdrawalProposed {
+        proposal_id: proposal.id,
+        proposer,
+        kind,
+        destination,
+        amount,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
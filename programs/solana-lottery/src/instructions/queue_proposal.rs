@@ -0,0 +1,86 @@
+use anchor_lang::prelude::*;
+use crate::state::governance::{ParameterChange, Proposal, ProposalStatus};
+use crate::state::config::ProtocolConfig;
+use crate::errors::LotteryError;
+
+#[event]
+pub struct ProposalQueued {
+    pub proposal_id: u64,
+    pub proposer: Pubkey,
+    pub change: ParameterChange,
+    pub eta: i64,
+    pub timestamp: i64,
+}
+
+#[derive(Accounts)]
+pub struct QueueProposal<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = config.is_admin(&admin.key()) @ LotteryError::Unauthorized
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = Proposal::SPACE,
+        seeds = [b"proposal", config.next_proposal_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    pub system_program: Program<'info, System>,
+    pub clock: Sysvar<'info, Clock>,
+}
+
+// Checks that need no other accounts; the rest happen at execution
+fn validate_change(change: &ParameterChange) -> Result<()> {
+    match *change {
+        ParameterChange::FeeBps(fee_bps) => ProtocolConfig::validate_fee_bps(fee_bps),
+        ParameterChange::TicketPrice { ticket_price, .. } => {
+            require!(ticket_price > 0, LotteryError::InvalidTicketPrice);
+            Ok(())
+        }
+        ParameterChange::PrizeTable { .. } => Ok(()),
+        ParameterChange::RegisterOracle(oracle) => {
+            require!(oracle != Pubkey::default(), LotteryError::InvalidProposal);
+            Ok(())
+        }
+        ParameterChange::DeregisterOracle(_) => Ok(()),
+        ParameterChange::GovernanceDelay(delay) => ProtocolConfig::validate_governance_delay(delay),
+    }
+}
+
+pub fn handler(ctx: Context<QueueProposal>, change: ParameterChange) -> Result<()> {
+    validate_change(&change)?;
+
+    let config = &mut ctx.accounts.config;
+    let clock = &ctx.accounts.clock;
+    let eta = clock.unix_timestamp
+        .checked_add(config.governance_delay)
+        .ok_or(LotteryError::ArithmeticError)?;
+
+    let proposal = &mut ctx.accounts.proposal;
+    proposal.id = config.take_proposal_id()?;
+    proposal.proposer = ctx.accounts.admin.key();
+    proposal.change = change;
+    proposal.status = ProposalStatus::Queued;
+    proposal.queued_at = clock.unix_timestamp;
+    proposal.eta = eta;
+    proposal.bump = ctx.bumps.proposal;
+
+    emit!(ProposalQueued {
+        proposal_id: proposal.id,
+        proposer: proposal.proposer,
+        change,
+        eta,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -1,15 +1,15 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use anchor_spl::token::{Token, TokenAccount};
 use crate::state::lottery::{Lottery, LotteryState, LotteryType};
 use crate::state::treasury::Treasury;
 use crate::errors::LotteryError;
+use crate::vault;
 use crate::utils;
 
 #[event]
 pub struct LotteryRecycled {
     pub lottery_id: u64,
     pub unclaimed_amount: u64,
-    pub new_end_time: i64,
     pub timestamp: i64,
     pub lottery_type: LotteryType,
 }
@@ -18,10 +18,9 @@ pub struct LotteryRecycled {
 pub struct RecycleUnclaimed<'info> {
     #[account(
         mut,
-        seeds = [b"lottery", lottery.lottery_type.discriminant().to_le_bytes().as_ref()],
+        seeds = [b"lottery", lottery.authority.as_ref(), lottery.id.to_le_bytes().as_ref()],
         bump = lottery.bump,
         constraint = lottery.state == LotteryState::Completed @ LotteryError::InvalidLotteryState,
-        constraint = !lottery.prize_claimed @ LotteryError::PrizeAlreadyClaimed,
         constraint = utils::is_claim_window_expired(lottery.timing.last_draw_timestamp)? @ LotteryError::ClaimWindowExpired
     )]
     pub lottery: Account<'info, Lottery>,
@@ -35,13 +34,14 @@ pub struct RecycleUnclaimed<'info> {
     
     #[account(
         mut,
-        constraint = lottery_token_account.owner == lottery.key()
+        address = lottery.config.vault @ LotteryError::InvalidVault
     )]
     pub lottery_token_account: Account<'info, TokenAccount>,
     
     #[account(
         mut,
-        constraint = treasury_token_account.owner == treasury.key()
+        address = treasury.token_account @ LotteryError::InvalidVault,
+        constraint = treasury_token_account.mint == lottery.config.mint @ LotteryError::InvalidMint
     )]
     pub treasury_token_account: Account<'info, TokenAccount>,
 
@@ -53,45 +53,33 @@ pub struct RecycleUnclaimed<'info> {
 pub fn handler(ctx: Context<RecycleUnclaimed>) -> Result<()> {
     let clock = &ctx.accounts.clock;
     
-    // Calculate unclaimed amount before mutating lottery
-    let unclaimed_amount = {
-        let lottery = &ctx.accounts.lottery;
-        lottery.state_data.current_pool_amount
-    };
+    // Shares never claimed within the window, plus the non-prize remainder
+    let unclaimed_amount = ctx.accounts.lottery.unclaimed_amount()?;
     
     // Transfer unclaimed funds to treasury
-    token::transfer(
-        CpiContext::new(
-            ctx.accounts.token_program.to_account_info(),
-            Transfer {
-                from: ctx.accounts.lottery_token_account.to_account_info(),
-                to: ctx.accounts.treasury_token_account.to_account_info(),
-                authority: ctx.accounts.lottery.to_account_info(),
-            },
-        ),
-        unclaimed_amount
+    vault::transfer_from_vault(
+        &ctx.accounts.lottery,
+        &ctx.accounts.lottery_token_account,
+        ctx.accounts.treasury_token_account.to_account_info(),
+        &ctx.accounts.token_program,
+        unclaimed_amount,
     )?;
     
     // Update lottery state after transfer
+    // Only the fee share counts as collected fees; unclaimed prize money is just balance
+    let fee = unclaimed_amount.min(ctx.accounts.lottery.state_data.treasury_fee);
+    ctx.accounts.treasury.record_deposit(unclaimed_amount, fee)?;
+    
     let lottery = &mut ctx.accounts.lottery;
     
-    // Reset lottery for new round
-    lottery.state = LotteryState::Created;
-    lottery.state_data.total_tickets = 0;
-    lottery.winner = None;
-    lottery.winner_ticket = None;
-    lottery.prize_claimed = false;
-    lottery.state_data.prize_amount = 0;
-    lottery.state_data.treasury_fee = 0;
-    lottery.state_data.current_pool_amount = 0;
-    lottery.timing.start_time = clock.unix_timestamp;
-    lottery.timing.end_time = clock.unix_timestamp + lottery.get_duration();
+    // Close out the round; the next round is a new lottery account from the series
+    lottery.state = LotteryState::Expired;
+    lottery.state_data.current_pool_amount = lottery.state_data.total_claimed;
     
     // Emit recycle event
     emit!(LotteryRecycled {
         lottery_id: lottery.id,
         unclaimed_amount,
-        new_end_time: lottery.timing.end_time,
         timestamp: clock.unix_timestamp,
         lottery_type: lottery.lottery_type,
     });
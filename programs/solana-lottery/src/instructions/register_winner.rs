@@ -0,0 +1,76 @@
+use anchor_lang::prelude::*;
+use crate::state::lottery::{Lottery, LotteryState};
+use crate::state::ticket::Ticket;
+use crate::state::config::ProtocolConfig;
+use crate::errors::LotteryError;
+use crate::utils;
+
+#[event]
+pub struct WinnerRegistered {
+    pub lottery_id: u64,
+    pub ticket_index: u64,
+    pub owner: Pubkey,
+    pub tier: u8,
+    pub tier_winners: u32,
+}
+
+#[derive(Accounts)]
+pub struct RegisterWinner<'info> {
+    #[account(
+        mut,
+        seeds = [b"lottery", lottery.authority.as_ref(), lottery.id.to_le_bytes().as_ref()],
+        bump = lottery.bump,
+        constraint = lottery.state == LotteryState::Completed @ LotteryError::InvalidLotteryState,
+        constraint = utils::is_registration_open(lottery.timing.last_draw_timestamp)? @ LotteryError::RegistrationClosed
+    )]
+    pub lottery: Account<'info, Lottery>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused.claims @ LotteryError::ProtocolPaused
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"ticket",
+            lottery.key().as_ref(),
+            ticket.round.to_le_bytes().as_ref(),
+            ticket.index.to_le_bytes().as_ref()
+        ],
+        bump = ticket.bump,
+        constraint = ticket.round == lottery.id @ LotteryError::InvalidTicket,
+        constraint = ticket.prize_tier.is_none() @ LotteryError::TicketAlreadyRegistered
+    )]
+    pub ticket: Account<'info, Ticket>,
+}
+
+// Permissionless: registering only ever credits the ticket owner, so anyone
+// may register a winning ticket on their behalf.
+pub fn handler(ctx: Context<RegisterWinner>) -> Result<()> {
+    let lottery = &mut ctx.accounts.lottery;
+    let ticket = &mut ctx.accounts.ticket;
+
+    let (_, _, tier) = utils::evaluate_ticket(lottery, ticket);
+    let tier = tier.ok_or(LotteryError::NotWinner)?;
+
+    let winners = &mut lottery.state_data.tier_winners[tier];
+    *winners = winners
+        .checked_add(1)
+        .ok_or(LotteryError::ArithmeticError)?;
+    let tier_winners = *winners;
+
+    ticket.prize_tier = Some(tier as u8);
+
+    emit!(WinnerRegistered {
+        lottery_id: lottery.id,
+        ticket_index: ticket.index,
+        owner: ticket.owner,
+        tier: tier as u8,
+        tier_winners,
+    });
+
+    Ok(())
+}
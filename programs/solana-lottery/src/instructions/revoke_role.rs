@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+use crate::state::role::{Role, RoleAssignment};
+use crate::state::config::ProtocolConfig;
+use crate::errors::LotteryError;
+
+#[event]
+pub struct RoleRevoked {
+    pub role: Role,
+    pub holder: Pubkey,
+    pub revoked_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[derive(Accounts)]
+pub struct RevokeRole<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = config.is_admin(&admin.key())
+            || RoleAssignment::held(&admin_role, Role::Admin, &admin.key()) @ LotteryError::Unauthorized
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+
+    /// Not needed when signing as the config admin
+    pub admin_role: Option<Account<'info, RoleAssignment>>,
+
+    // Rent goes back to the revoking admin
+    #[account(
+        mut,
+        close = admin,
+        seeds = [b"role", assignment.role.seed().as_ref(), assignment.holder.as_ref()],
+        bump = assignment.bump
+    )]
+    pub assignment: Account<'info, RoleAssignment>,
+
+    pub clock: Sysvar<'info, Clock>,
+}
+
+pub fn handler(ctx: Context<RevokeRole>) -> Result<()> {
+    let assignment = &ctx.accounts.assignment;
+
+    emit!(RoleRevoked {
+        role: assignment.role,
+        holder: assignment.holder,
+        revoked_by: ctx.accounts.admin.key(),
+        timestamp: ctx.accounts.clock.unix_timestamp,
+    });
+
+    Ok(())
+}
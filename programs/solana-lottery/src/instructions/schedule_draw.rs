@@ -1,6 +1,10 @@
 use anchor_lang::prelude::*;
-use crate::state::lottery::{Lottery, LotteryState};
+use crate::state::lottery::{DrawCommitment, Lottery, LotteryState, RandomnessSource};
+use crate::state::role::{Role, RoleAssignment};
+use crate::state::config::ProtocolConfig;
 use crate::errors::LotteryError;
+use crate::commit_reveal::REVEAL_WINDOW_SLOTS;
+use crate::utils::REFUND_WINDOW;
 
 #[event]
 pub struct DrawScheduled {
@@ -14,23 +18,66 @@ pub struct DrawScheduled {
 pub struct ScheduleDraw<'info> {
     #[account(
         mut,
-        seeds = [b"lottery", lottery.lottery_type.discriminant().to_le_bytes().as_ref()],
+        seeds = [b"lottery", lottery.authority.as_ref(), lottery.id.to_le_bytes().as_ref()],
         bump = lottery.bump,
         constraint = lottery.state == LotteryState::Open @ LotteryError::InvalidLotteryState,
-        constraint = Clock::get()?.unix_timestamp >= lottery.timing.end_time @ LotteryError::InvalidTimeRange,
-        constraint = lottery.state_data.total_tickets > 0 @ LotteryError::MinPoolNotReached
+        constraint = Clock::get()?.unix_timestamp >= lottery.timing.end_time @ LotteryError::InvalidTimeRange
     )]
     pub lottery: Account<'info, Lottery>,
     
     #[account(mut)]
     pub admin: Signer<'info>,
+
+    #[account(constraint = drawer_role.grants(Role::Drawer, &admin.key()) @ LotteryError::Unauthorized)]
+    pub drawer_role: Account<'info, RoleAssignment>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused.draws @ LotteryError::ProtocolPaused
+    )]
+    pub config: Account<'info, ProtocolConfig>,
     
     pub clock: Sysvar<'info, Clock>,
 }
 
-pub fn handler(ctx: Context<ScheduleDraw>) -> Result<()> {
+pub fn handler(ctx: Context<ScheduleDraw>, commitment: Option<[u8; 32]>) -> Result<()> {
     let lottery = &mut ctx.accounts.lottery;
     let clock = &ctx.accounts.clock;
+    let min_pool_reached = lottery.state_data.current_pool_amount >= lottery.config.min_pool_amount;
+    
+    // Under-funded rounds are never drawn; buyers get their tickets refunded
+    if !min_pool_reached {
+        lottery.state = LotteryState::Refunding;
+        lottery.timing.refund_deadline = clock.unix_timestamp
+            .checked_add(REFUND_WINDOW)
+            .ok_or(LotteryError::ArithmeticError)?;
+        
+        emit!(DrawScheduled {
+            lottery_id: lottery.id,
+            total_tickets: lottery.state_data.total_tickets,
+            timestamp: clock.unix_timestamp,
+            min_pool_reached,
+        });
+        return Ok(());
+    }
+    
+    // Commit-reveal rounds lock in the operator's hashed secret now
+    if lottery.config.randomness == RandomnessSource::CommitReveal {
+        require!(
+            ctx.accounts.admin.key() == lottery.authority,
+            LotteryError::Unauthorized
+        );
+        let commitment = commitment.ok_or(LotteryError::InvalidReveal)?;
+        lottery.draw_commitment = DrawCommitment {
+            commitment,
+            commit_slot: clock.slot,
+            reveal_deadline_slot: clock.slot
+                .checked_add(REVEAL_WINDOW_SLOTS)
+                .ok_or(LotteryError::ArithmeticError)?,
+            ..Default::default()
+        };
+    }
     
     // Update lottery state
     lottery.state = LotteryState::Drawing;
@@ -40,7 +87,7 @@ pub fn handler(ctx: Context<ScheduleDraw>) -> Result<()> {
         lottery_id: lottery.id,
         total_tickets: lottery.state_data.total_tickets,
         timestamp: clock.unix_timestamp,
-        min_pool_reached: lottery.state_data.current_pool_amount >= lottery.config.min_pool_amount,
+        min_pool_reached,
     });
     
     Ok(())
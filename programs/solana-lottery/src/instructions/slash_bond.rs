@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+use crate::state::lottery::{Lottery, LotteryState, RandomnessSource};
+use crate::errors::LotteryError;
+use crate::utils::REFUND_WINDOW;
+
+#[event]
+pub struct BondSlashed {
+    pub lottery_id: u64,
+    pub bond_amount: u64,
+    pub total_tickets: u64,
+    pub timestamp: i64,
+}
+
+#[derive(Accounts)]
+pub struct SlashBond<'info> {
+    #[account(
+        mut,
+        seeds = [b"lottery", lottery.authority.as_ref(), lottery.id.to_le_bytes().as_ref()],
+        bump = lottery.bump,
+        constraint = lottery.state == LotteryState::Drawing @ LotteryError::InvalidLotteryState,
+        constraint = lottery.config.randomness == RandomnessSource::CommitReveal @ LotteryError::InvalidRandomnessSource
+    )]
+    pub lottery: Account<'info, Lottery>,
+
+    pub clock: Sysvar<'info, Clock>,
+}
+
+// Permissionless: once the reveal deadline passes, anyone can hand the bond
+// to ticket holders, which removes any benefit from withholding a bad reveal.
+pub fn handler(ctx: Context<SlashBond>) -> Result<()> {
+    let lottery = &mut ctx.accounts.lottery;
+    let clock = &ctx.accounts.clock;
+
+    require!(
+        clock.slot > lottery.draw_commitment.reveal_deadline_slot,
+        LotteryError::RevealWindowOpen
+    );
+
+    lottery.state = LotteryState::Slashed;
+    lottery.timing.refund_deadline = clock.unix_timestamp
+        .checked_add(REFUND_WINDOW)
+        .ok_or(LotteryError::ArithmeticError)?;
+
+    emit!(BondSlashed {
+        lottery_id: lottery.id,
+        bond_amount: lottery.config.bond_amount,
+        total_tickets: lottery.state_data.total_tickets,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
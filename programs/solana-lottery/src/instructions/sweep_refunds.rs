@@ -0,0 +1,111 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount};
+use crate::state::lottery::{Lottery, LotteryState};
+use crate::state::treasury::Treasury;
+use crate::errors::LotteryError;
+use crate::vault;
+
+#[event]
+pub struct RefundsSwept {
+    pub lottery_id: u64,
+    pub bond_returned: u64,
+    pub swept_amount: u64,
+    pub timestamp: i64,
+}
+
+#[derive(Accounts)]
+pub struct SweepRefunds<'info> {
+    #[account(
+        mut,
+        seeds = [b"lottery", lottery.authority.as_ref(), lottery.id.to_le_bytes().as_ref()],
+        bump = lottery.bump,
+        constraint = matches!(lottery.state, LotteryState::Refunding | LotteryState::Slashed) @ LotteryError::InvalidLotteryState,
+        constraint = Clock::get()?.unix_timestamp > lottery.timing.refund_deadline @ LotteryError::RefundWindowOpen
+    )]
+    pub lottery: Account<'info, Lottery>,
+
+    #[account(
+        mut,
+        address = lottery.config.vault @ LotteryError::InvalidVault
+    )]
+    pub lottery_token_account: Account<'info, TokenAccount>,
+
+    /// Gets the operator bond back; required if an unslashed lottery holds one
+    #[account(
+        mut,
+        constraint = authority_token_account.owner == lottery.authority @ LotteryError::InvalidTokenAccount,
+        constraint = authority_token_account.mint == lottery.config.mint @ LotteryError::InvalidMint
+    )]
+    pub authority_token_account: Option<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    #[account(
+        mut,
+        address = treasury.token_account @ LotteryError::InvalidVault,
+        constraint = treasury_token_account.mint == lottery.config.mint @ LotteryError::InvalidMint
+    )]
+    pub treasury_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub clock: Sysvar<'info, Clock>,
+}
+
+// Permissionless once the refund window closes. In a `Refunding` round the
+// bond was never at risk since no draw took place, so it goes back to the
+// operator. A slashed bond stays forfeited. Everything else left in the vault
+// (unclaimed refunds, bond shares and rounding dust) goes to the treasury.
+pub fn handler(ctx: Context<SweepRefunds>) -> Result<()> {
+    let lottery = &ctx.accounts.lottery;
+    let clock = &ctx.accounts.clock;
+
+    let bond_returned = match lottery.state {
+        LotteryState::Slashed => 0,
+        _ => lottery.config.bond_amount,
+    };
+    if bond_returned > 0 {
+        let to = ctx.accounts.authority_token_account
+            .as_ref()
+            .ok_or(LotteryError::InvalidBond)?;
+        vault::transfer_from_vault(
+            lottery,
+            &ctx.accounts.lottery_token_account,
+            to.to_account_info(),
+            &ctx.accounts.token_program,
+            bond_returned,
+        )?;
+    }
+
+    let swept_amount = ctx.accounts.lottery_token_account.amount
+        .checked_sub(bond_returned)
+        .ok_or(LotteryError::ArithmeticError)?;
+    if swept_amount > 0 {
+        vault::transfer_from_vault(
+            lottery,
+            &ctx.accounts.lottery_token_account,
+            ctx.accounts.treasury_token_account.to_account_info(),
+            &ctx.accounts.token_program,
+            swept_amount,
+        )?;
+    }
+
+    ctx.accounts.treasury.record_deposit(swept_amount, 0)?;
+
+    let lottery = &mut ctx.accounts.lottery;
+    lottery.state = LotteryState::Expired;
+    lottery.state_data.current_pool_amount = 0;
+
+    emit!(RefundsSwept {
+        lottery_id: lottery.id,
+        bond_returned,
+        swept_amount,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
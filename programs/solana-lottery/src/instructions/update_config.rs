@@ -0,0 +1,59 @@
+use anchor_lang::prelude::*;
+use crate::state::config::{PauseFlags, ProtocolConfig};
+use crate::errors::LotteryError;
+
+#[event]
+pub struct ConfigUpdated {
+    pub admin: Pubkey,
+    pub fee_bps: u16,
+    pub paused: PauseFlags,
+    pub timestamp: i64,
+}
+
+/// Fields left as `None` keep their current value. Fee and oracle changes go
+/// through timelocked governance proposals instead.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct UpdateConfigParams {
+    pub admin: Option<Pubkey>,
+    pub allowed_mints: Option<Vec<Pubkey>>,
+    pub paused: Option<PauseFlags>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = config.is_admin(&admin.key()) @ LotteryError::Unauthorized
+    )]
+    pub config: Account<'info, ProtocolConfig>,
+
+    pub clock: Sysvar<'info, Clock>,
+}
+
+pub fn handler(ctx: Context<UpdateConfig>, params: UpdateConfigParams) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+
+    if let Some(admin) = params.admin {
+        require!(admin != Pubkey::default(), LotteryError::InvalidConfig);
+        config.admin = admin;
+    }
+    if let Some(mints) = params.allowed_mints {
+        config.set_allowed_mints(&mints)?;
+    }
+    if let Some(paused) = params.paused {
+        config.paused = paused;
+    }
+
+    emit!(ConfigUpdated {
+        admin: config.admin,
+        fee_bps: config.fee_bps,
+        paused: config.paused,
+        timestamp: ctx.accounts.clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -6,41 +6,146 @@ pub mod state;
 pub mod errors;
 pub mod utils;
 pub mod instructions;
+pub mod vrf;
+pub mod commit_reveal;
+pub mod numbers;
+pub mod vault;
 
 use instructions::{
-    create_lottery::{self, CreateLottery},
+    initialize_config::{self, InitializeConfig, InitializeConfigParams},
+    update_config::{self, UpdateConfig, UpdateConfigParams},
+    initialize_treasury::{self, InitializeTreasury, InitializeTreasuryParams},
+    initialize_series::{self, InitializeSeries},
+    create_prize_table::{self, CreatePrizeTable},
+    create_lottery::{self, CreateLottery, CreateLotteryParams},
+    open_lottery::{self, OpenLottery},
     buy_ticket::{self, BuyTicket},
     schedule_draw::{self, ScheduleDraw},
     execute_draw::{self, ExecuteDraw},
+    execute_draw_vrf::{self, ExecuteDrawVrf},
+    execute_draw_reveal::{self, ExecuteDrawReveal},
+    slash_bond::{self, SlashBond},
+    cancel_lottery::{self, CancelLottery},
+    claim_bond_refund::{self, ClaimBondRefund},
+    claim_refund::{self, ClaimRefund},
+    sweep_refunds::{self, SweepRefunds},
+    register_winner::{self, RegisterWinner},
     claim_prize::{self, ClaimPrize},
+    claim_prizes_batch::{self, ClaimPrizesBatch},
+    payout_winners::{self, PayoutWinners},
     distribute_prize::{self, DistributePrize},
     recycle_unclaimed::{self, RecycleUnclaimed},
-    withdraw_treasury::{self, WithdrawTreasury},
+    propose_withdrawal::{self, ProposeWithdrawal},
+    approve_withdrawal::{self, ApproveWithdrawal},
+    execute_withdrawal::{self, ExecuteWithdrawal},
+    queue_proposal::{self, QueueProposal},
+    cancel_proposal::{self, CancelProposal},
+    execute_proposal::{self, ExecuteProposal},
+    grant_role::{self, GrantRole},
+    revoke_role::{self, RevokeRole},
 };
-use state::LotteryType;
+use state::{CancelReason, ParameterChange, PrizeTier, Role, WithdrawalKind};
 
 #[program]
 pub mod solana_lottery {
     use super::*;
 
-    pub fn create_lottery(ctx: Context<CreateLottery>, lottery_type: LotteryType) -> Result<()> {
-        create_lottery::handler(ctx, lottery_type)
+    pub fn initialize_config(
+        ctx: Context<InitializeConfig>,
+        params: InitializeConfigParams,
+    ) -> Result<()> {
+        initialize_config::handler(ctx, params)
     }
 
-    pub fn buy_ticket(ctx: Context<BuyTicket>, amount: u8) -> Result<()> {
-        buy_ticket::handler(ctx, amount)
+    pub fn update_config(ctx: Context<UpdateConfig>, params: UpdateConfigParams) -> Result<()> {
+        update_config::handler(ctx, params)
     }
 
-    pub fn schedule_draw(ctx: Context<ScheduleDraw>) -> Result<()> {
-        schedule_draw::handler(ctx)
+    pub fn initialize_treasury(
+        ctx: Context<InitializeTreasury>,
+        params: InitializeTreasuryParams,
+    ) -> Result<()> {
+        initialize_treasury::handler(ctx, params)
+    }
+
+    pub fn initialize_series(ctx: Context<InitializeSeries>) -> Result<()> {
+        initialize_series::handler(ctx)
+    }
+
+    pub fn create_prize_table(
+        ctx: Context<CreatePrizeTable>,
+        table_id: u64,
+        tiers: Vec<PrizeTier>,
+    ) -> Result<()> {
+        create_prize_table::handler(ctx, table_id, tiers)
+    }
+
+    pub fn create_lottery(ctx: Context<CreateLottery>, params: CreateLotteryParams) -> Result<()> {
+        create_lottery::handler(ctx, params)
+    }
+
+    pub fn open_lottery(ctx: Context<OpenLottery>) -> Result<()> {
+        open_lottery::handler(ctx)
+    }
+
+    pub fn buy_ticket(ctx: Context<BuyTicket>, numbers: [u8; 6], bonus_number: Option<u8>) -> Result<()> {
+        buy_ticket::handler(ctx, numbers, bonus_number)
+    }
+
+    pub fn schedule_draw(ctx: Context<ScheduleDraw>, commitment: Option<[u8; 32]>) -> Result<()> {
+        schedule_draw::handler(ctx, commitment)
     }
 
     pub fn execute_draw(ctx: Context<ExecuteDraw>) -> Result<()> {
         execute_draw::handler(ctx)
     }
 
-    pub fn claim_prize(ctx: Context<ClaimPrize>, user_numbers: [u8; 6]) -> Result<()> {
-        claim_prize::handler(ctx, user_numbers)
+    pub fn execute_draw_vrf(ctx: Context<ExecuteDrawVrf>, proof: [u8; 80]) -> Result<()> {
+        execute_draw_vrf::handler(ctx, proof)
+    }
+
+    pub fn execute_draw_reveal(ctx: Context<ExecuteDrawReveal>, secret: [u8; 32]) -> Result<()> {
+        execute_draw_reveal::handler(ctx, secret)
+    }
+
+    pub fn slash_bond(ctx: Context<SlashBond>) -> Result<()> {
+        slash_bond::handler(ctx)
+    }
+
+    pub fn cancel_lottery(ctx: Context<CancelLottery>, reason: CancelReason) -> Result<()> {
+        cancel_lottery::handler(ctx, reason)
+    }
+
+    pub fn claim_bond_refund(ctx: Context<ClaimBondRefund>) -> Result<()> {
+        claim_bond_refund::handler(ctx)
+    }
+
+    pub fn claim_refund(ctx: Context<ClaimRefund>) -> Result<()> {
+        claim_refund::handler(ctx)
+    }
+
+    pub fn sweep_refunds(ctx: Context<SweepRefunds>) -> Result<()> {
+        sweep_refunds::handler(ctx)
+    }
+
+    pub fn register_winner(ctx: Context<RegisterWinner>) -> Result<()> {
+        register_winner::handler(ctx)
+    }
+
+    pub fn claim_prize(ctx: Context<ClaimPrize>) -> Result<()> {
+        claim_prize::handler(ctx)
+    }
+
+    pub fn claim_prizes_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ClaimPrizesBatch<'info>>,
+    ) -> Result<()> {
+        claim_prizes_batch::handler(ctx)
+    }
+
+    pub fn payout_winners<'info>(
+        ctx: Context<'_, '_, 'info, 'info, PayoutWinners<'info>>,
+    ) -> Result<()> {
+        payout_winners::handler(ctx)
     }
 
     pub fn distribute_prize(ctx: Context<DistributePrize>) -> Result<()> {
@@ -51,8 +156,41 @@ pub mod solana_lottery {
         recycle_unclaimed::handler(ctx)
     }
 
-    pub fn withdraw_treasury(ctx: Context<WithdrawTreasury>, amount: u64) -> Result<()> {
-        withdraw_treasury::handler(ctx, amount)
+    pub fn propose_withdrawal(
+        ctx: Context<ProposeWithdrawal>,
+        kind: WithdrawalKind,
+        destination: Pubkey,
+        amount: u64,
+    ) -> Result<()> {
+        propose_withdrawal::handler(ctx, kind, destination, amount)
+    }
+
+    pub fn approve_withdrawal(ctx: Context<ApproveWithdrawal>) -> Result<()> {
+        approve_withdrawal::handler(ctx)
+    }
+
+    pub fn execute_withdrawal(ctx: Context<ExecuteWithdrawal>) -> Result<()> {
+        execute_withdrawal::handler(ctx)
+    }
+
+    pub fn queue_proposal(ctx: Context<QueueProposal>, change: ParameterChange) -> Result<()> {
+        queue_proposal::handler(ctx, change)
+    }
+
+    pub fn cancel_proposal(ctx: Context<CancelProposal>) -> Result<()> {
+        cancel_proposal::handler(ctx)
+    }
+
+    pub fn execute_proposal(ctx: Context<ExecuteProposal>) -> Result<()> {
+        execute_proposal::handler(ctx)
+    }
+
+    pub fn grant_role(ctx: Context<GrantRole>, role: Role, holder: Pubkey) -> Result<()> {
+        grant_role::handler(ctx, role, holder)
+    }
+
+    pub fn revoke_role(ctx: Context<RevokeRole>) -> Result<()> {
+        revoke_role::handler(ctx)
     }
 }
 
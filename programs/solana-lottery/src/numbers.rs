@@ -0,0 +1,327 @@
+use anchor_lang::prelude::*;
+use crate::errors::LotteryError;
+use sha2::{Sha256, Digest};
+
+// Winning-number generation. Numbers are drawn from a stream of hash blocks
+// with rejection sampling, so every value in the domain is equally likely
+// (a plain `byte % n` favours the low values whenever n does not divide 256).
+
+pub const MAX_PICKS: usize = 6;
+
+/// The numbers a lottery draws and accepts on tickets: `picks` values in
+/// `min..=max`, either digit-style with repeats or `picks` distinct values.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct NumberDomain {
+    pub picks: u8,
+    pub min: u8,
+    pub max: u8,
+    pub allow_repeats: bool,
+}
+
+impl Default for NumberDomain {
+    // Six digits 0-9 with repetition
+    fn default() -> Self {
+        Self {
+            picks: MAX_PICKS as u8,
+            min: 0,
+            max: 9,
+            allow_repeats: true,
+        }
+    }
+}
+
+impl NumberDomain {
+    pub const SPACE: usize = 1 + 1 + 1 + 1;
+
+    #[inline]
+    pub fn range(&self) -> u16 {
+        (self.max - self.min) as u16 + 1
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.picks > 0 && self.picks as usize <= MAX_PICKS && self.min <= self.max,
+            LotteryError::InvalidNumberDomain
+        );
+        require!(
+            self.allow_repeats || self.range() >= self.picks as u16,
+            LotteryError::InvalidNumberDomain
+        );
+        Ok(())
+    }
+
+    /// Ticket numbers occupy the first `picks` slots; the rest must be zero.
+    pub fn validate_ticket(&self, numbers: &[u8; MAX_PICKS]) -> Result<()> {
+        let (picked, unused) = numbers.split_at(self.picks as usize);
+        require!(
+            picked.iter().all(|n| *n >= self.min && *n <= self.max),
+            LotteryError::InvalidTicketNumbers
+        );
+        require!(unused.iter().all(|n| *n == 0), LotteryError::InvalidTicketNumbers);
+
+        if !self.allow_repeats {
+            for (i, n) in picked.iter().enumerate() {
+                require!(!picked[..i].contains(n), LotteryError::InvalidTicketNumbers);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Single bonus number drawn from its own range, Powerball-style.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub struct BonusDomain {
+    pub min: u8,
+    pub max: u8,
+}
+
+impl BonusDomain {
+    pub const SPACE: usize = 1 + 1;
+
+    #[inline]
+    pub fn range(&self) -> u16 {
+        (self.max - self.min) as u16 + 1
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        require!(self.min <= self.max, LotteryError::InvalidNumberDomain);
+        Ok(())
+    }
+
+    pub fn contains(&self, n: u8) -> bool {
+        n >= self.min && n <= self.max
+    }
+}
+
+/// A ticket must carry a bonus number exactly when the lottery draws one.
+pub fn validate_bonus(bonus_domain: &Option<BonusDomain>, bonus_number: Option<u8>) -> Result<()> {
+    let valid = match (bonus_domain, bonus_number) {
+        (Some(domain), Some(n)) => domain.contains(n),
+        (None, None) => true,
+        _ => false,
+    };
+    require!(valid, LotteryError::InvalidTicketNumbers);
+    Ok(())
+}
+
+/// Endless byte stream: sha256(seed || counter) for counter = 0, 1, ...
+pub struct HashStream {
+    seed: [u8; 32],
+    counter: u32,
+    block: [u8; 32],
+    pos: usize,
+}
+
+impl HashStream {
+    pub fn new(seed: &[u8; 32]) -> Self {
+        Self {
+            seed: *seed,
+            counter: 0,
+            block: [0u8; 32],
+            pos: 32,
+        }
+    }
+
+    fn next_byte(&mut self) -> u8 {
+        if self.pos == self.block.len() {
+            let mut hasher = Sha256::new();
+            hasher.update(self.seed);
+            hasher.update(self.counter.to_le_bytes());
+            self.block = hasher.finalize().into();
+            self.counter = self.counter.wrapping_add(1);
+            self.pos = 0;
+        }
+        let byte = self.block[self.pos];
+        self.pos += 1;
+        byte
+    }
+
+    /// Uniform value in `0..n` for `1 <= n <= 256`, rejecting bytes from the
+    /// incomplete top bucket.
+    pub fn next_below(&mut self, n: u16) -> u8 {
+        let limit = 256 - (256 % n);
+        loop {
+            let byte = self.next_byte() as u16;
+            if byte < limit {
+                return (byte % n) as u8;
+            }
+        }
+    }
+}
+
+/// Draws the main numbers and, if configured, the bonus number from the
+/// same stream.
+pub fn draw_numbers(
+    seed: &[u8; 32],
+    domain: &NumberDomain,
+    bonus_domain: &Option<BonusDomain>,
+) -> ([u8; MAX_PICKS], Option<u8>) {
+    let mut stream = HashStream::new(seed);
+    let mut numbers = [0u8; MAX_PICKS];
+    let range = domain.range();
+
+    for i in 0..domain.picks as usize {
+        loop {
+            let n = domain.min + stream.next_below(range);
+            if domain.allow_repeats || !numbers[..i].contains(&n) {
+                numbers[i] = n;
+                break;
+            }
+        }
+    }
+
+    let bonus = bonus_domain.map(|bonus| bonus.min + stream.next_below(bonus.range()));
+    (numbers, bonus)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pick(picks: u8, min: u8, max: u8, allow_repeats: bool) -> NumberDomain {
+        NumberDomain {
+            picks,
+            min,
+            max,
+            allow_repeats,
+        }
+    }
+
+    // Stream positioned at the start of a chosen block
+    fn stream_with_block(bytes: &[u8]) -> HashStream {
+        let mut stream = HashStream::new(&[0u8; 32]);
+        stream.block[..bytes.len()].copy_from_slice(bytes);
+        stream.pos = 0;
+        stream
+    }
+
+    fn seed(i: u32) -> [u8; 32] {
+        let mut seed = [0u8; 32];
+        seed[..4].copy_from_slice(&i.to_le_bytes());
+        seed
+    }
+
+    #[test]
+    fn next_below_rejects_incomplete_top_bucket() {
+        // n = 10 accepts 0..250; 250 and 255 fall in the partial bucket
+        let mut stream = stream_with_block(&[250, 255, 249, 7]);
+        assert_eq!(stream.next_below(10), 9);
+        assert_eq!(stream.next_below(10), 7);
+    }
+
+    #[test]
+    fn next_below_accepts_every_byte_when_n_divides_256() {
+        let mut stream = stream_with_block(&[255, 0, 128]);
+        assert_eq!(stream.next_below(256), 255);
+        assert_eq!(stream.next_below(256), 0);
+        assert_eq!(stream.next_below(128), 0);
+    }
+
+    #[test]
+    fn next_below_with_single_value() {
+        let mut stream = HashStream::new(&seed(1));
+        assert!((0..100).all(|_| stream.next_below(1) == 0));
+    }
+
+    #[test]
+    fn next_below_covers_the_range_evenly() {
+        let mut stream = HashStream::new(&seed(2));
+        let mut counts = [0u32; 10];
+        for _ in 0..10_000 {
+            counts[stream.next_below(10) as usize] += 1;
+        }
+        // Expected 1000 each; catches out-of-range or unreachable values
+        assert!(counts.iter().all(|c| (850..1150).contains(c)), "{counts:?}");
+    }
+
+    #[test]
+    fn stream_refills_across_blocks() {
+        let mut stream = HashStream::new(&seed(3));
+        let first: Vec<u8> = (0..64).map(|_| stream.next_byte()).collect();
+        assert_ne!(first[..32], first[32..]);
+        assert_eq!(stream.counter, 2);
+    }
+
+    #[test]
+    fn draw_is_deterministic() {
+        let domain = pick(6, 1, 49, false);
+        let bonus = Some(BonusDomain { min: 1, max: 10 });
+        assert!(draw_numbers(&seed(4), &domain, &bonus) == draw_numbers(&seed(4), &domain, &bonus));
+        assert!(draw_numbers(&seed(4), &domain, &bonus) != draw_numbers(&seed(5), &domain, &bonus));
+    }
+
+    #[test]
+    fn draw_without_repeats_is_distinct() {
+        // Range equal to picks forces a permutation of 1..=6
+        let domain = pick(6, 1, 6, false);
+        for i in 0..200 {
+            let (mut numbers, bonus) = draw_numbers(&seed(i), &domain, &None);
+            assert!(bonus.is_none());
+            numbers.sort_unstable();
+            assert_eq!(numbers, [1, 2, 3, 4, 5, 6]);
+        }
+
+        let domain = pick(5, 1, 49, false);
+        for i in 0..200 {
+            let (numbers, _) = draw_numbers(&seed(i), &domain, &None);
+            assert!(domain.validate_ticket(&numbers).is_ok(), "{numbers:?}");
+        }
+    }
+
+    #[test]
+    fn draw_stays_in_range() {
+        let domain = pick(6, 0, 9, true);
+        let bonus_domain = Some(BonusDomain { min: 20, max: 25 });
+        for i in 0..200 {
+            let (numbers, bonus) = draw_numbers(&seed(i), &domain, &bonus_domain);
+            assert!(numbers.iter().all(|n| *n <= 9));
+            assert!(validate_bonus(&bonus_domain, bonus).is_ok());
+        }
+    }
+
+    #[test]
+    fn domain_validation() {
+        assert!(NumberDomain::default().validate().is_ok());
+        assert!(pick(6, 1, 49, false).validate().is_ok());
+        assert!(pick(6, 1, 6, false).validate().is_ok());
+        assert!(pick(0, 0, 9, true).validate().is_err());
+        assert!(pick(7, 0, 9, true).validate().is_err());
+        assert!(pick(3, 9, 0, true).validate().is_err());
+        // Five distinct values cannot fill six picks
+        assert!(pick(6, 1, 5, false).validate().is_err());
+        assert!(pick(6, 1, 5, true).validate().is_ok());
+    }
+
+    #[test]
+    fn ticket_numbers_must_be_in_range() {
+        let domain = pick(6, 1, 49, false);
+        assert!(domain.validate_ticket(&[1, 2, 3, 4, 5, 49]).is_ok());
+        assert!(domain.validate_ticket(&[0, 2, 3, 4, 5, 6]).is_err());
+        assert!(domain.validate_ticket(&[1, 2, 3, 4, 5, 50]).is_err());
+    }
+
+    #[test]
+    fn unused_ticket_slots_must_be_zero() {
+        let domain = pick(4, 1, 20, false);
+        assert!(domain.validate_ticket(&[1, 2, 3, 4, 0, 0]).is_ok());
+        assert!(domain.validate_ticket(&[1, 2, 3, 4, 5, 0]).is_err());
+        assert!(domain.validate_ticket(&[1, 2, 3, 4, 0, 1]).is_err());
+    }
+
+    #[test]
+    fn duplicate_ticket_numbers_depend_on_domain() {
+        let numbers = [3, 1, 4, 1, 5, 9];
+        assert!(pick(6, 0, 9, false).validate_ticket(&numbers).is_err());
+        assert!(pick(6, 0, 9, true).validate_ticket(&numbers).is_ok());
+    }
+
+    #[test]
+    fn bonus_presence_must_match_domain() {
+        let domain = Some(BonusDomain { min: 1, max: 10 });
+        assert!(validate_bonus(&domain, Some(1)).is_ok());
+        assert!(validate_bonus(&domain, Some(11)).is_err());
+        assert!(validate_bonus(&domain, None).is_err());
+        assert!(validate_bonus(&None, Some(1)).is_err());
+        assert!(validate_bonus(&None, None).is_ok());
+    }
+}
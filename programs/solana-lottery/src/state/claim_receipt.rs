@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+
+/// Proof that a ticket's prize was paid. One PDA per (round, ticket); its
+/// `init` in `claim_prize` is what rules out a second claim.
+#[account]
+#[derive(Default)]
+pub struct ClaimReceipt {
+    pub lottery: Pubkey,
+    pub round: u64,
+    pub ticket_index: u64,
+    pub owner: Pubkey,
+    pub tier: u8,
+    pub amount: u64,
+    pub claimed_at: i64,
+    pub bump: u8,
+}
+
+impl ClaimReceipt {
+    pub const SPACE: usize = 8 + // discriminator
+        32 + // lottery
+        8 + // round
+        8 + // ticket_index
+        32 + // owner
+        1 + // tier
+        8 + // amount
+        8 + // claimed_at
+        1; // bump
+}
@@ -0,0 +1,134 @@
+use anchor_lang::prelude::*;
+use crate::errors::LotteryError;
+use crate::state::prize_table::BPS_DENOMINATOR;
+
+pub const MAX_ALLOWED_MINTS: usize = 4;
+pub const MAX_REGISTERED_ORACLES: usize = 8;
+pub const MAX_FEE_BPS: u16 = 2_000; // 20%
+pub const MIN_GOVERNANCE_DELAY: i64 = 60 * 60; // 1 hour
+
+/// Emergency switches; each one blocks a single class of instruction.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct PauseFlags {
+    pub lottery_creation: bool,
+    pub ticket_sales: bool,
+    pub draws: bool,
+    pub claims: bool,
+}
+
+impl PauseFlags {
+    pub const SPACE: usize = 1 + 1 + 1 + 1;
+}
+
+/// Protocol-wide settings, one PDA at `[b"config"]`.
+///
+/// There is no separate fee recipient. Fees only ever move into the treasury
+/// vault, recorded once as `Treasury.token_account` by `initialize_treasury`;
+/// `complete_draw` books the fee and `distribute_prize`, `recycle_unclaimed`
+/// and `sweep_refunds` pin their destination to that address. A second copy
+/// here could point somewhere the treasury multisig does not control.
+#[account]
+#[derive(Default)]
+pub struct ProtocolConfig {
+    pub admin: Pubkey,
+    pub fee_bps: u16, // copied into each lottery at creation
+    pub mint_count: u8,
+    pub allowed_mints: [Pubkey; MAX_ALLOWED_MINTS],
+    pub paused: PauseFlags,
+    // Pyth price accounts and VRF oracle keys lotteries may be created with
+    pub oracle_count: u8,
+    pub oracles: [Pubkey; MAX_REGISTERED_ORACLES],
+    pub bump: u8,
+    // Parameter changes wait this long between queueing and execution
+    pub governance_delay: i64,
+    pub next_proposal_id: u64,
+}
+
+impl ProtocolConfig {
+    pub const SPACE: usize = 8 + // discriminator
+        32 + // admin
+        2 + // fee_bps
+        1 + // mint_count
+        32 * MAX_ALLOWED_MINTS + // allowed_mints
+        PauseFlags::SPACE + // paused
+        1 + // oracle_count
+        32 * MAX_REGISTERED_ORACLES + // oracles
+        1 + // bump
+        8 + // governance_delay
+        8; // next_proposal_id
+
+    pub fn validate_fee_bps(fee_bps: u16) -> Result<()> {
+        require!(
+            fee_bps <= MAX_FEE_BPS && (fee_bps as u64) < BPS_DENOMINATOR,
+            LotteryError::InvalidConfig
+        );
+        Ok(())
+    }
+
+    pub fn set_allowed_mints(&mut self, mints: &[Pubkey]) -> Result<()> {
+        require!(mints.len() <= MAX_ALLOWED_MINTS, LotteryError::InvalidConfig);
+        self.allowed_mints = [Pubkey::default(); MAX_ALLOWED_MINTS];
+        self.allowed_mints[..mints.len()].copy_from_slice(mints);
+        self.mint_count = mints.len() as u8;
+        Ok(())
+    }
+
+    pub fn set_oracles(&mut self, oracles: &[Pubkey]) -> Result<()> {
+        require!(oracles.len() <= MAX_REGISTERED_ORACLES, LotteryError::InvalidConfig);
+        self.oracles = [Pubkey::default(); MAX_REGISTERED_ORACLES];
+        self.oracles[..oracles.len()].copy_from_slice(oracles);
+        self.oracle_count = oracles.len() as u8;
+        Ok(())
+    }
+
+    pub fn validate_governance_delay(delay: i64) -> Result<()> {
+        require!(delay >= MIN_GOVERNANCE_DELAY, LotteryError::InvalidConfig);
+        Ok(())
+    }
+
+    pub fn register_oracle(&mut self, oracle: Pubkey) -> Result<()> {
+        require!(
+            oracle != Pubkey::default() && !self.is_oracle_registered(&oracle),
+            LotteryError::InvalidConfig
+        );
+        let count = self.oracle_count as usize;
+        require!(count < MAX_REGISTERED_ORACLES, LotteryError::InvalidConfig);
+        self.oracles[count] = oracle;
+        self.oracle_count += 1;
+        Ok(())
+    }
+
+    pub fn deregister_oracle(&mut self, oracle: &Pubkey) -> Result<()> {
+        let count = self.oracle_count as usize;
+        let index = self.oracles[..count]
+            .iter()
+            .position(|key| key == oracle)
+            .ok_or(LotteryError::OracleNotRegistered)?;
+        // Order is irrelevant; move the last entry into the gap
+        self.oracles[index] = self.oracles[count - 1];
+        self.oracles[count - 1] = Pubkey::default();
+        self.oracle_count -= 1;
+        Ok(())
+    }
+
+    pub fn take_proposal_id(&mut self) -> Result<u64> {
+        let id = self.next_proposal_id;
+        self.next_proposal_id = self.next_proposal_id
+            .checked_add(1)
+            .ok_or(LotteryError::ArithmeticError)?;
+        Ok(id)
+    }
+
+    #[inline]
+    pub fn is_admin(&self, key: &Pubkey) -> bool {
+        self.admin == *key
+    }
+
+    pub fn is_mint_allowed(&self, mint: &Pubkey) -> bool {
+        self.allowed_mints[..self.mint_count as usize].contains(mint)
+    }
+
+    pub fn is_oracle_registered(&self, oracle: &Pubkey) -> bool {
+        self.oracles[..self.oracle_count as usize].contains(oracle)
+    }
+}
@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+
+/// A single parameter change, applied as a whole when its proposal executes.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum ParameterChange {
+    FeeBps(u16), // treasury fee snapshotted by new lotteries
+    TicketPrice { lottery: Pubkey, ticket_price: u64 },
+    PrizeTable { lottery: Pubkey, prize_table: Pubkey },
+    RegisterOracle(Pubkey),
+    DeregisterOracle(Pubkey),
+    GovernanceDelay(i64),
+}
+
+impl ParameterChange {
+    // Largest variant is `PrizeTable`
+    pub const SPACE: usize = 1 + 32 + 32;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ProposalStatus {
+    #[default]
+    Queued,
+    Executed,
+    Cancelled,
+}
+
+/// Timelocked parameter change, one PDA per id at `[b"proposal", id]`.
+#[account]
+pub struct Proposal {
+    pub id: u64,
+    pub proposer: Pubkey,
+    pub change: ParameterChange,
+    pub status: ProposalStatus,
+    pub queued_at: i64,
+    pub eta: i64, // earliest execution time
+    pub bump: u8,
+}
+
+impl Proposal {
+    pub const SPACE: usize = 8 + // discriminator
+        8 + // id
+        32 + // proposer
+        ParameterChange::SPACE + // change
+        1 + // status
+        8 + // queued_at
+        8 + // eta
+        1; // bump
+
+    #[inline]
+    pub fn is_queued(&self) -> bool {
+        self.status == ProposalStatus::Queued
+    }
+}
@@ -1,4 +1,9 @@
 use anchor_lang::prelude::*;
+use crate::errors::LotteryError;
+use crate::numbers::{BonusDomain, NumberDomain};
+use crate::state::prize_table::{PrizeTiers, MAX_PRIZE_TIERS};
+use crate::state::treasury::Treasury;
+use crate::vrf::{OUTPUT_SIZE, PROOF_SIZE};
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default)]
 pub enum LotteryState {
@@ -7,7 +12,9 @@ pub enum LotteryState {
     Open,
     Drawing,
     Completed,
-    Expired
+    Expired,
+    Slashed,  // Operator missed the reveal; tickets refundable with a bond share
+    Refunding // Minimum pool not reached; tickets refundable at full price
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default)]
@@ -18,12 +25,62 @@ pub enum LotteryType {
     Monthly  // 10 USDC, 30d, min 1000 USDC
 }
 
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RandomnessSource {
+    #[default]
+    PriceFeed,    // Hash of the Pyth account, clock and slot
+    Vrf,          // ECVRF proof from the registered oracle
+    CommitReveal, // Operator secret mixed with a later slot hash
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MatchMode {
+    #[default]
+    Positional, // Digit games: position i must equal position i
+    Unordered,  // Pick k of n: matches are the set intersection
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum CancelReason {
+    Misconfiguration,
+    OracleOutage,
+    Other,
+}
+
 // Split into smaller components to reduce stack usage
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
 pub struct LotteryConfig {
     pub ticket_price: u64,
     pub min_pool_amount: u64,
     pub pyth_price_account: Pubkey,
+    pub max_price_age: u64, // seconds
+    pub number_domain: NumberDomain,
+    pub bonus_domain: Option<BonusDomain>,
+    pub prize_tiers: PrizeTiers,
+    pub match_mode: MatchMode,
+    pub randomness: RandomnessSource,
+    pub vrf_oracle: Pubkey,
+    pub bond_amount: u64,
+    pub fee_bps: u16, // treasury fee at creation
+    pub mint: Pubkey, // the only token this lottery accepts and pays out
+    pub vault: Pubkey,
+}
+
+impl LotteryConfig {
+    pub const SPACE: usize = 8 + // ticket_price
+        8 + // min_pool_amount
+        32 + // pyth_price_account
+        8 + // max_price_age
+        NumberDomain::SPACE + // number_domain
+        (1 + BonusDomain::SPACE) + // bonus_domain (Option)
+        PrizeTiers::SPACE + // prize_tiers
+        1 + // match_mode
+        1 + // randomness
+        32 + // vrf_oracle
+        8 + // bond_amount
+        2 + // fee_bps
+        32 + // mint
+        32; // vault
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
@@ -32,6 +89,13 @@ pub struct LotteryStateData {
     pub current_pool_amount: u64,
     pub prize_amount: u64,
     pub treasury_fee: u64,
+    // Tickets registered per prize tier, indexed like `config.prize_tiers`
+    pub tier_winners: [u32; MAX_PRIZE_TIERS],
+    // Running totals over every `ClaimReceipt` of the round
+    pub total_claimed: u64,
+    pub claimed_tickets: u32,
+    // Next ticket index for the `payout_winners` crank
+    pub payout_cursor: u64,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
@@ -39,6 +103,39 @@ pub struct LotteryTiming {
     pub start_time: i64,
     pub end_time: i64,
     pub last_draw_timestamp: i64,
+    pub refund_deadline: i64,
+}
+
+/// VRF input, proof and output kept on-chain so anyone can re-verify a draw:
+/// `proof` is an RFC 9381 ECVRF proof of `alpha` under `config.vrf_oracle`
+/// and `output` its `beta`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct DrawProof {
+    pub alpha: [u8; 32],
+    pub proof: [u8; PROOF_SIZE],
+    pub output: [u8; OUTPUT_SIZE],
+}
+
+impl Default for DrawProof {
+    fn default() -> Self {
+        Self {
+            alpha: [0u8; 32],
+            proof: [0u8; PROOF_SIZE],
+            output: [0u8; OUTPUT_SIZE],
+        }
+    }
+}
+
+/// Commit-reveal draw data. The operator commits `sha256(secret)` when the
+/// draw is scheduled and must reveal before `reveal_deadline_slot`; the
+/// revealed secret and slot hash are kept so the draw can be recomputed.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct DrawCommitment {
+    pub commitment: [u8; 32],
+    pub commit_slot: u64,
+    pub reveal_deadline_slot: u64,
+    pub secret: [u8; 32],
+    pub slot_hash: [u8; 32],
 }
 
 #[account]
@@ -48,13 +145,15 @@ pub struct Lottery {
     pub lottery_type: LotteryType,
     pub state: LotteryState,
     pub bump: u8,
+    pub authority: Pubkey,
     pub config: LotteryConfig,
     pub timing: LotteryTiming,
     pub state_data: LotteryStateData,
-    pub winner_ticket: Option<u64>,
-    pub winner: Option<Pubkey>,
-    pub prize_claimed: bool,
     pub winning_numbers: [u8; 6],
+    pub bonus_number: Option<u8>,
+    pub draw_proof: DrawProof,
+    pub draw_commitment: DrawCommitment,
+    pub cancel_reason: Option<CancelReason>,
 }
 
 impl Lottery {
@@ -63,13 +162,15 @@ impl Lottery {
         1 + // lottery_type
         1 + // state
         1 + // bump
-        (8 + 8 + 32) + // config
-        (8 + 8 + 8) + // timing
-        (8 + 8 + 8 + 8) + // state_data
-        9 + // winner_ticket (Option)
-        33 + // winner (Option<Pubkey>)
-        1 + // prize_claimed
-        6; // winning_numbers
+        32 + // authority
+        LotteryConfig::SPACE + // config
+        (8 + 8 + 8 + 8) + // timing
+        (8 + 8 + 8 + 8 + 4 * MAX_PRIZE_TIERS + 8 + 4 + 8) + // state_data
+        6 + // winning_numbers
+        2 + // bonus_number (Option)
+        (32 + PROOF_SIZE + OUTPUT_SIZE) + // draw_proof
+        (32 + 8 + 8 + 32 + 32) + // draw_commitment
+        2; // cancel_reason (Option)
 
     pub fn get_min_pool_amount(&self) -> u64 {
         match self.lottery_type {
@@ -87,6 +188,35 @@ impl Lottery {
         }
     }
 
+    /// Price and prize rules may only change before the first ticket is sold.
+    pub fn is_configurable(&self) -> bool {
+        matches!(self.state, LotteryState::Created | LotteryState::Open)
+            && self.state_data.total_tickets == 0
+    }
+
+    /// Tickets registered across all tiers.
+    pub fn registered_winners(&self) -> u64 {
+        self.state_data.tier_winners.iter().map(|n| *n as u64).sum()
+    }
+
+    /// Prize pool of a round that only just reached its minimum; draws never
+    /// happen below it.
+    pub fn min_prize_pool(&self) -> Result<u64> {
+        let fee = Treasury::calculate_fee(self.config.fee_bps, self.config.min_pool_amount)?;
+        let pool = self.config.min_pool_amount
+            .checked_sub(fee)
+            .ok_or(LotteryError::ArithmeticError)?;
+        Ok(pool)
+    }
+
+    /// Pool still held by the lottery after every claim so far.
+    pub fn unclaimed_amount(&self) -> Result<u64> {
+        let unclaimed = self.state_data.current_pool_amount
+            .checked_sub(self.state_data.total_claimed)
+            .ok_or(LotteryError::ArithmeticError)?;
+        Ok(unclaimed)
+    }
+
     pub fn get_duration(&self) -> i64 {
         match self.lottery_type {
             LotteryType::Daily => 24 * 60 * 60, // 24 hours
@@ -1,7 +1,21 @@
 
 pub mod treasury;
 pub mod lottery;
+pub mod ticket;
+pub mod series;
+pub mod prize_table;
+pub mod claim_receipt;
+pub mod config;
+pub mod governance;
+pub mod role;
 
 
 pub use treasury::*;
-pub use lottery::*; 
\ No newline at end of file
+pub use lottery::*;
+pub use ticket::*;
+pub use series::*;
+pub use prize_table::*;
+pub use claim_receipt::*;
+pub use config::*; 
+pub use governance::*; 
+pub use role::*; 
\ No newline at end of file
@@ -0,0 +1,225 @@
+use anchor_lang::prelude::*;
+use crate::errors::LotteryError;
+
+pub const MAX_PRIZE_TIERS: usize = 8;
+pub const BPS_DENOMINATOR: u64 = 10_000;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PrizeKind {
+    #[default]
+    PoolShareBps, // `value` is basis points of the prize pool
+    FixedAmount,  // `value` is a token amount, capped at the prize pool
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub struct PrizeTier {
+    pub min_matches: u8,
+    pub bonus_required: bool,
+    pub kind: PrizeKind,
+    pub value: u64,
+}
+
+impl PrizeTier {
+    pub const SPACE: usize = 1 + 1 + 1 + 8;
+
+    // Higher tiers need more matches; on equal matches the bonus tier ranks first
+    #[inline]
+    fn rank(&self) -> (u8, bool) {
+        (self.min_matches, self.bonus_required)
+    }
+}
+
+/// Ordered tier list, best tier first. Lotteries keep their own copy so the
+/// payout rules of a round cannot change once tickets are sold.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct PrizeTiers {
+    pub count: u8,
+    pub tiers: [PrizeTier; MAX_PRIZE_TIERS],
+}
+
+impl PrizeTiers {
+    pub const SPACE: usize = 1 + MAX_PRIZE_TIERS * PrizeTier::SPACE;
+
+    pub fn from_slice(tiers: &[PrizeTier]) -> Result<Self> {
+        require!(
+            !tiers.is_empty() && tiers.len() <= MAX_PRIZE_TIERS,
+            LotteryError::InvalidPrizeTable
+        );
+        let mut result = Self {
+            count: tiers.len() as u8,
+            ..Default::default()
+        };
+        result.tiers[..tiers.len()].copy_from_slice(tiers);
+        Ok(result)
+    }
+
+    #[inline]
+    pub fn active(&self) -> &[PrizeTier] {
+        &self.tiers[..self.count as usize]
+    }
+
+    /// Checks ordering and that pool shares never add up to more than 100%.
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.count > 0 && self.count as usize <= MAX_PRIZE_TIERS,
+            LotteryError::InvalidPrizeTable
+        );
+
+        let tiers = self.active();
+        let mut total_bps: u64 = 0;
+        for (i, tier) in tiers.iter().enumerate() {
+            require!(tier.min_matches > 0, LotteryError::InvalidPrizeTable);
+            if i > 0 {
+                require!(tiers[i - 1].rank() > tier.rank(), LotteryError::InvalidPrizeTable);
+            }
+            if tier.kind == PrizeKind::PoolShareBps {
+                total_bps = total_bps
+                    .checked_add(tier.value)
+                    .ok_or(LotteryError::ArithmeticError)?;
+            }
+        }
+        require!(total_bps <= BPS_DENOMINATOR, LotteryError::InvalidPrizeTable);
+        Ok(())
+    }
+
+    /// Checks the tiers are reachable for a game of `picks` numbers.
+    pub fn validate_for(&self, picks: u8, has_bonus: bool) -> Result<()> {
+        self.validate()?;
+        require!(
+            self.active()
+                .iter()
+                .all(|t| t.min_matches <= picks && (has_bonus || !t.bonus_required)),
+            LotteryError::InvalidPrizeTable
+        );
+        Ok(())
+    }
+
+    /// Fixed prizes do not shrink with the pool, so together with the pool
+    /// shares they must fit the smallest prize pool a drawn round can have.
+    /// Larger pools only leave more room.
+    pub fn validate_for_pool(&self, min_prize_pool: u64) -> Result<()> {
+        let mut total_bps: u64 = 0;
+        let mut total_fixed: u64 = 0;
+        for tier in self.active() {
+            match tier.kind {
+                PrizeKind::PoolShareBps => {
+                    total_bps = total_bps
+                        .checked_add(tier.value)
+                        .ok_or(LotteryError::ArithmeticError)?;
+                }
+                PrizeKind::FixedAmount => {
+                    total_fixed = total_fixed
+                        .checked_add(tier.value)
+                        .ok_or(LotteryError::ArithmeticError)?;
+                }
+            }
+        }
+
+        let pool_shares = (min_prize_pool as u128)
+            .checked_mul(total_bps as u128)
+            .ok_or(LotteryError::ArithmeticError)?
+            .checked_div(BPS_DENOMINATOR as u128)
+            .ok_or(LotteryError::ArithmeticError)? as u64;
+        let left_for_fixed = min_prize_pool
+            .checked_sub(pool_shares)
+            .ok_or(LotteryError::InvalidPrizeTable)?;
+        require!(total_fixed <= left_for_fixed, LotteryError::InvalidPrizeTable);
+        Ok(())
+    }
+
+    /// Index of the best tier the result qualifies for.
+    pub fn position(&self, matching: u8, bonus_matched: bool) -> Option<usize> {
+        self.active()
+            .iter()
+            .position(|t| matching >= t.min_matches && (bonus_matched || !t.bonus_required))
+    }
+
+    /// Best tier the result qualifies for.
+    pub fn find(&self, matching: u8, bonus_matched: bool) -> Option<&PrizeTier> {
+        self.position(matching, bonus_matched).map(|i| &self.tiers[i])
+    }
+}
+
+/// Reusable prize table. Lotteries copy it at creation.
+#[account]
+#[derive(Default)]
+pub struct PrizeTable {
+    pub authority: Pubkey,
+    pub table_id: u64,
+    pub tiers: PrizeTiers,
+    pub bump: u8,
+}
+
+impl PrizeTable {
+    pub const SPACE: usize = 8 + // discriminator
+        32 + // authority
+        8 + // table_id
+        PrizeTiers::SPACE + // tiers
+        1; // bump
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn share(min_matches: u8, bps: u64) -> PrizeTier {
+        PrizeTier {
+            min_matches,
+            bonus_required: false,
+            kind: PrizeKind::PoolShareBps,
+            value: bps,
+        }
+    }
+
+    fn fixed(min_matches: u8, amount: u64) -> PrizeTier {
+        PrizeTier {
+            min_matches,
+            bonus_required: false,
+            kind: PrizeKind::FixedAmount,
+            value: amount,
+        }
+    }
+
+    #[test]
+    fn pool_shares_may_not_exceed_the_pool() {
+        assert!(PrizeTiers::from_slice(&[share(6, 6_000), share(5, 4_000)]).unwrap().validate().is_ok());
+        assert!(PrizeTiers::from_slice(&[share(6, 6_000), share(5, 4_001)]).unwrap().validate().is_err());
+    }
+
+    #[test]
+    fn tiers_must_be_strictly_ordered() {
+        assert!(PrizeTiers::from_slice(&[share(5, 100), share(6, 100)]).unwrap().validate().is_err());
+        assert!(PrizeTiers::from_slice(&[share(5, 100), share(5, 100)]).unwrap().validate().is_err());
+
+        let mut bonus = share(5, 100);
+        bonus.bonus_required = true;
+        assert!(PrizeTiers::from_slice(&[bonus, share(5, 100)]).unwrap().validate().is_ok());
+        assert!(PrizeTiers::from_slice(&[share(5, 100), bonus]).unwrap().validate().is_err());
+    }
+
+    #[test]
+    fn fixed_prizes_count_against_the_pool() {
+        // 100% in shares leaves nothing for a fixed prize
+        let tiers = PrizeTiers::from_slice(&[share(6, 10_000), fixed(5, 1)]).unwrap();
+        assert!(tiers.validate().is_ok());
+        assert!(tiers.validate_for_pool(1_000_000).is_err());
+
+        let tiers = PrizeTiers::from_slice(&[share(6, 9_000), fixed(5, 100_000)]).unwrap();
+        assert!(tiers.validate_for_pool(1_000_000).is_ok());
+        assert!(tiers.validate_for_pool(999_990).is_err());
+
+        let tiers = PrizeTiers::from_slice(&[fixed(6, 600_000), fixed(5, 400_001)]).unwrap();
+        assert!(tiers.validate_for_pool(1_000_000).is_err());
+    }
+
+    #[test]
+    fn position_picks_the_best_qualifying_tier() {
+        let mut bonus = share(5, 1_000);
+        bonus.bonus_required = true;
+        let tiers = PrizeTiers::from_slice(&[share(6, 5_000), bonus, share(5, 500)]).unwrap();
+        assert_eq!(tiers.position(6, false), Some(0));
+        assert_eq!(tiers.position(5, true), Some(1));
+        assert_eq!(tiers.position(5, false), Some(2));
+        assert_eq!(tiers.position(4, true), None);
+    }
+}
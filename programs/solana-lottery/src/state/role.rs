@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Role {
+    Admin,     // Grants and revokes roles
+    Operator,  // Creates and opens lotteries
+    Drawer,    // Schedules and executes draws
+    Treasurer, // Executes approved treasury withdrawals
+}
+
+impl Role {
+    /// Seed byte for the `[b"role", role, holder]` PDA.
+    #[inline]
+    pub fn seed(self) -> [u8; 1] {
+        [self as u8]
+    }
+}
+
+/// One PDA per (role, holder) at `[b"role", role, holder]`. Revoking a role
+/// closes its account, so an existing assignment is always a live grant.
+#[account]
+pub struct RoleAssignment {
+    pub role: Role,
+    pub holder: Pubkey,
+    pub granted_by: Pubkey,
+    pub granted_at: i64,
+    pub bump: u8,
+}
+
+impl RoleAssignment {
+    pub const SPACE: usize = 8 + // discriminator
+        1 + // role
+        32 + // holder
+        32 + // granted_by
+        8 + // granted_at
+        1; // bump
+
+    /// Constraint helper: `constraint = x_role.grants(Role::X, &signer.key())`.
+    #[inline]
+    pub fn grants(&self, role: Role, key: &Pubkey) -> bool {
+        self.role == role && self.holder == *key
+    }
+
+    /// For accounts where the role is one of several ways in.
+    #[inline]
+    pub fn held(assignment: &Option<Account<RoleAssignment>>, role: Role, key: &Pubkey) -> bool {
+        assignment.as_ref().is_some_and(|assignment| assignment.grants(role, key))
+    }
+}
@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+use crate::errors::LotteryError;
+
+/// Per-operator round counter. Every lottery created by an operator takes the
+/// next round number, which is also part of the lottery PDA seeds.
+#[account]
+#[derive(Default)]
+pub struct LotterySeries {
+    pub authority: Pubkey,
+    pub next_round: u64,
+    pub bump: u8,
+}
+
+impl LotterySeries {
+    pub const SPACE: usize = 8 + // discriminator
+        32 + // authority
+        8 + // next_round
+        1; // bump
+
+    pub fn take_round(&mut self) -> Result<u64> {
+        let round = self.next_round;
+        self.next_round = self.next_round
+            .checked_add(1)
+            .ok_or(LotteryError::ArithmeticError)?;
+        Ok(round)
+    }
+}
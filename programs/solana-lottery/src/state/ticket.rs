@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(Default)]
+pub struct Ticket {
+    pub lottery: Pubkey,
+    pub owner: Pubkey,
+    pub round: u64,
+    pub index: u64,
+    pub numbers: [u8; 6],
+    pub bonus_number: Option<u8>,
+    pub purchase_slot: u64,
+    pub refunded: bool,
+    pub prize_tier: Option<u8>,
+    pub bump: u8,
+}
+
+impl Ticket {
+    pub const SPACE: usize = 8 + // discriminator
+        32 + // lottery
+        32 + // owner
+        8 + // round
+        8 + // index
+        6 + // numbers
+        2 + // bonus_number (Option)
+        8 + // purchase_slot
+        1 + // refunded
+        2 + // prize_tier (Option)
+        1; // bump
+}
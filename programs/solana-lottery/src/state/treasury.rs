@@ -1,62 +1,69 @@
 use anchor_lang::prelude::*;
 use crate::errors::LotteryError;
 
-#[derive(Accounts)]
-pub struct WithdrawTreasury<'info> {
-    #[account(
-        mut,
-        has_one = authority @ LotteryError::Unauthorized,
-    )]
-    pub treasury: Account<'info, Treasury>,
-    
-    #[account(mut)]
-    pub token_account: AccountInfo<'info>,
-    
-    #[account(signer)]
-    pub authority: AccountInfo<'info>,
-    
-    pub system_program: Program<'info, System>,
-}
+pub const MAX_TREASURY_SIGNERS: usize = 10;
 
 #[account]
 pub struct Treasury {
     pub balance: u64,
-    pub fee_bps: u16,     // 250 for 2.5%
     pub authority: Pubkey,
     pub token_account: Pubkey,
     pub total_fees_collected: u64,
     pub last_withdrawal: i64,
     pub time_locked: i64,
     pub bump: u8,
+    // M-of-N withdrawal signers; approvals are tracked per proposal
+    pub signer_count: u8,
+    pub threshold: u8,
+    pub signers: [Pubkey; MAX_TREASURY_SIGNERS],
+    pub next_proposal_id: u64,
 }
 
 impl Treasury {
     pub const SPACE: usize = 8 + // discriminator
         8 + // balance
-        2 + // fee_bps
         32 + // authority
         32 + // token_account
         8 + // total_fees_collected
         8 + // last_withdrawal
         8 + // time_locked
-        1; // bump
+        1 + // bump
+        1 + // signer_count
+        1 + // threshold
+        32 * MAX_TREASURY_SIGNERS + // signers
+        8; // next_proposal_id
 
-    pub fn collect_fees(&mut self, amount: u64) -> Result<u64> {
+    pub fn calculate_fee(fee_bps: u16, amount: u64) -> Result<u64> {
         let fee = (amount as u128)
-            .checked_mul(self.fee_bps as u128)
+            .checked_mul(fee_bps as u128)
             .ok_or(LotteryError::ArithmeticError)?
             .checked_div(10000)
             .ok_or(LotteryError::ArithmeticError)? as u64;
+        Ok(fee)
+    }
+
+    /// Books tokens that arrived in the vault. `fee` is the part of `amount`
+    /// that is protocol fee; the rest is recycled prize or refund money.
+    pub fn record_deposit(&mut self, amount: u64, fee: u64) -> Result<()> {
+        require!(fee <= amount, LotteryError::InvalidPrizeDistribution);
 
         self.balance = self.balance
-            .checked_add(fee)
+            .checked_add(amount)
             .ok_or(LotteryError::ArithmeticError)?;
 
         self.total_fees_collected = self.total_fees_collected
             .checked_add(fee)
             .ok_or(LotteryError::ArithmeticError)?;
 
-        Ok(fee)
+        Ok(())
+    }
+
+    /// Books tokens paid out of the vault other than by `withdraw`.
+    pub fn record_payout(&mut self, amount: u64) -> Result<()> {
+        self.balance = self.balance
+            .checked_sub(amount)
+            .ok_or(LotteryError::InsufficientTreasuryBalance)?;
+        Ok(())
     }
 
     pub fn withdraw(&mut self, amount: u64, clock: &Clock) -> Result<()> {
@@ -78,7 +85,43 @@ impl Treasury {
     }
 
     pub fn is_authorized_signer(&self, signer: &Signer) -> bool {
-        self.authority == signer.key()
+        self.signer_index(&signer.key()).is_some()
+    }
+
+    pub fn signer_index(&self, key: &Pubkey) -> Option<usize> {
+        self.signers[..self.signer_count as usize]
+            .iter()
+            .position(|signer| signer == key)
+    }
+
+    /// Replaces the signer set. Keys must be distinct and the threshold
+    /// reachable.
+    pub fn set_signers(&mut self, signers: &[Pubkey], threshold: u8) -> Result<()> {
+        require!(
+            !signers.is_empty() && signers.len() <= MAX_TREASURY_SIGNERS,
+            LotteryError::InvalidMultisig
+        );
+        require!(
+            threshold > 0 && threshold as usize <= signers.len(),
+            LotteryError::InvalidMultisig
+        );
+        for (i, signer) in signers.iter().enumerate() {
+            require!(!signers[..i].contains(signer), LotteryError::InvalidMultisig);
+        }
+
+        self.signers = [Pubkey::default(); MAX_TREASURY_SIGNERS];
+        self.signers[..signers.len()].copy_from_slice(signers);
+        self.signer_count = signers.len() as u8;
+        self.threshold = threshold;
+        Ok(())
+    }
+
+    pub fn take_proposal_id(&mut self) -> Result<u64> {
+        let id = self.next_proposal_id;
+        self.next_proposal_id = self.next_proposal_id
+            .checked_add(1)
+            .ok_or(LotteryError::ArithmeticError)?;
+        Ok(id)
     }
 }
 
@@ -87,4 +130,58 @@ pub struct TreasuryWithdrawal {
     pub amount: u64,
     pub authority: Pubkey,
     pub timestamp: i64,
-}
\ No newline at end of file
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WithdrawalKind {
+    #[default]
+    Tokens,       // From the treasury vault to a token account of the same mint
+    RentLamports, // Excess lamports above the treasury PDA's rent-exempt minimum
+}
+
+/// A pending treasury withdrawal. Signers approve by index into
+/// `Treasury.signers`; it can run once `threshold` approvals are in and the
+/// treasury timelock has passed.
+#[account]
+#[derive(Default)]
+pub struct WithdrawalProposal {
+    pub treasury: Pubkey,
+    pub id: u64,
+    pub proposer: Pubkey,
+    pub kind: WithdrawalKind,
+    pub destination: Pubkey,
+    pub amount: u64,
+    pub approvals: u16, // bit i set once signer i approved
+    pub approval_count: u8,
+    pub executed: bool,
+    pub created_at: i64,
+    pub bump: u8,
+}
+
+impl WithdrawalProposal {
+    pub const SPACE: usize = 8 + // discriminator
+        32 + // treasury
+        8 + // id
+        32 + // proposer
+        1 + // kind
+        32 + // destination
+        8 + // amount
+        2 + // approvals
+        1 + // approval_count
+        1 + // executed
+        8 + // created_at
+        1; // bump
+
+    /// Records `index`'s approval; false if it was already recorded.
+    pub fn approve(&mut self, index: usize) -> Result<bool> {
+        let bit = 1u16 << index;
+        if self.approvals & bit != 0 {
+            return Ok(false);
+        }
+        self.approvals |= bit;
+        self.approval_count = self.approval_count
+            .checked_add(1)
+            .ok_or(LotteryError::ArithmeticError)?;
+        Ok(true)
+    }
+}
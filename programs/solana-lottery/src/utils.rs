@@ -1,11 +1,51 @@
 use anchor_lang::prelude::*;
 use crate::errors::LotteryError;
-use crate::state::lottery::LotteryType;
+use crate::state::lottery::{Lottery, LotteryType, MatchMode};
+use crate::state::ticket::Ticket;
+use crate::state::prize_table::{PrizeKind, PrizeTier, PrizeTiers, BPS_DENOMINATOR};
+use crate::numbers::{self, BonusDomain, NumberDomain};
+use pyth_sdk_solana::state::{load_price_account, SolanaPriceAccount};
+use pyth_sdk_solana::Price;
 use sha2::{Sha256, Digest};
 
-pub const MAX_TICKETS_PER_TX: u8 = 5;
 pub const CLAIM_WINDOW: i64 = 14 * 24 * 60 * 60; // 14 days
-pub const PRIZE_TIERS: [u8; 4] = [60, 25, 10, 5]; // Percentages for 6, 5, 4, 3 matching digits
+pub const REFUND_WINDOW: i64 = 14 * 24 * 60 * 60; // 14 days
+pub const REGISTRATION_WINDOW: i64 = 2 * 24 * 60 * 60; // 2 days, inside the claim window
+pub const PRICE_DECIMALS: u8 = 6; // ticket prices and pool minimums are in USDC units
+pub const WITHDRAWAL_TIMELOCK: i64 = 24 * 60 * 60; // 24 hours between treasury withdrawals
+pub const DEFAULT_GOVERNANCE_DELAY: i64 = 24 * 60 * 60; // 24 hours from queueing to execution
+pub const DEFAULT_MAX_PRICE_AGE: u64 = 60; // seconds
+pub const MAX_PRICE_CONFIDENCE_BPS: u64 = 200; // conf must be within 2% of price
+
+/// Built-in tier keyed by main numbers missed and whether the bonus matched.
+/// Used when a lottery is created without a `PrizeTable`.
+pub struct DefaultTier {
+    pub misses: u8,
+    pub bonus: bool,
+    pub percentage: u8,
+}
+
+const fn tier(misses: u8, bonus: bool, percentage: u8) -> DefaultTier {
+    DefaultTier { misses, bonus, percentage }
+}
+
+// All, all-1, all-2, all-3 matching numbers
+pub const PRIZE_TIERS: [DefaultTier; 4] = [
+    tier(0, false, 60),
+    tier(1, false, 25),
+    tier(2, false, 10),
+    tier(3, false, 5),
+];
+
+// Bonus-ball games: e.g. with six picks, tier(1, true, _) is "5 + bonus"
+pub const BONUS_PRIZE_TIERS: [DefaultTier; 6] = [
+    tier(0, true, 50),
+    tier(0, false, 20),
+    tier(1, true, 12),
+    tier(1, false, 8),
+    tier(2, true, 6),
+    tier(2, false, 4),
+];
 
 #[inline]
 pub fn get_current_timestamp() -> Result<i64> {
@@ -13,54 +53,89 @@ pub fn get_current_timestamp() -> Result<i64> {
 }
 
 // Split random number generation into smaller functions
-fn hash_data(data: &[u8], time: i64, slot: u64) -> [u8; 32] {
+fn hash_data(price: &Price, time: i64, slot: u64) -> [u8; 32] {
     let mut hasher = Sha256::new();
-    hasher.update(&data[..24]);
+    hasher.update(price.price.to_le_bytes());
+    hasher.update(price.conf.to_le_bytes());
+    hasher.update(price.publish_time.to_le_bytes());
     hasher.update(time.to_le_bytes());
     hasher.update(slot.to_le_bytes());
     hasher.finalize().into()
 }
 
-fn convert_hash_to_numbers(hash: &[u8; 32]) -> [u8; 6] {
-    let mut numbers = [0u8; 6];
-    for i in 0..6 {
-        numbers[i] = (hash[i] % 10) as u8;
-    }
-    numbers
+/// Parses a Pyth price account and returns its price if it was published
+/// within `max_age` seconds and its confidence interval is tight enough.
+pub fn load_fresh_price(price_feed: &AccountInfo, current_time: i64, max_age: u64) -> Result<Price> {
+    let data = price_feed.try_borrow_data()?;
+    let price_account: &SolanaPriceAccount = load_price_account(&data)
+        .map_err(|_| LotteryError::InvalidPythFeed)?;
+
+    // pyth-sdk-solana 0.10 is built on solana-program 2.x, so convert the key
+    let feed_key = solana_program::pubkey::Pubkey::new_from_array(price_feed.key.to_bytes());
+    let price = price_account
+        .to_price_feed(&feed_key)
+        .get_price_no_older_than(current_time, max_age)
+        .ok_or(LotteryError::StalePrice)?;
+
+    require!(price.price > 0, LotteryError::InvalidPythFeed);
+    let max_conf = (price.price as u128)
+        .checked_mul(MAX_PRICE_CONFIDENCE_BPS as u128)
+        .ok_or(LotteryError::ArithmeticError)?
+        / 10_000;
+    require!((price.conf as u128) <= max_conf, LotteryError::InvalidPythFeed);
+
+    Ok(price)
 }
 
-pub fn generate_random_number(price_feed: &AccountInfo) -> Result<[u8; 6]> {
+pub fn generate_random_number(
+    price_feed: &AccountInfo,
+    max_price_age: u64,
+    domain: &NumberDomain,
+    bonus_domain: &Option<BonusDomain>,
+) -> Result<([u8; 6], Option<u8>)> {
     let current_time = Clock::get()?.unix_timestamp;
     let current_slot = Clock::get()?.slot;
     
-    let data = price_feed.try_borrow_data()?;
-    let hash = hash_data(&data, current_time, current_slot);
-    Ok(convert_hash_to_numbers(&hash))
+    let price = load_fresh_price(price_feed, current_time, max_price_age)?;
+    let hash = hash_data(&price, current_time, current_slot);
+    Ok(numbers::draw_numbers(&hash, domain, bonus_domain))
 }
 
-#[inline]
-pub fn calculate_prize_amount(matching_digits: u8, total_pool: u64) -> Result<u64> {
-    require!(matching_digits >= 3 && matching_digits <= 6, LotteryError::InvalidPrizeDistribution);
+/// Converts the built-in table into pool-share tiers for a game of `picks`
+/// numbers, dropping tiers that would need fewer than one match.
+pub fn default_prize_tiers(picks: u8, has_bonus: bool) -> Result<PrizeTiers> {
+    let defaults: &[DefaultTier] = if has_bonus { &BONUS_PRIZE_TIERS } else { &PRIZE_TIERS };
+    let mut tiers = PrizeTiers::default();
     
-    let tier_index = (6 - matching_digits) as usize;
-    let percentage = PRIZE_TIERS[tier_index];
-    
-    let amount = (total_pool as u128)
-        .checked_mul(percentage as u128)
-        .ok_or(LotteryError::ArithmeticError)?
-        .checked_div(100)
-        .ok_or(LotteryError::ArithmeticError)? as u64;
+    for default in defaults.iter().filter(|t| t.misses < picks) {
+        tiers.tiers[tiers.count as usize] = PrizeTier {
+            min_matches: picks - default.misses,
+            bonus_required: default.bonus,
+            kind: PrizeKind::PoolShareBps,
+            value: default.percentage as u64 * 100,
+        };
+        tiers.count += 1;
+    }
     
-    Ok(amount)
+    tiers.validate_for(picks, has_bonus)?;
+    Ok(tiers)
 }
 
 #[inline]
-pub fn validate_ticket_purchase(num_tickets: u8) -> Result<()> {
-    require!(
-        num_tickets > 0 && num_tickets <= MAX_TICKETS_PER_TX,
-        LotteryError::ExceededTicketLimit
-    );
-    Ok(())
+pub fn calculate_prize_amount(tier: &PrizeTier, total_pool: u64) -> Result<u64> {
+    let amount = match tier.kind {
+        PrizeKind::PoolShareBps => {
+            require!(tier.value <= BPS_DENOMINATOR, LotteryError::InvalidPrizeDistribution);
+            (total_pool as u128)
+                .checked_mul(tier.value as u128)
+                .ok_or(LotteryError::ArithmeticError)?
+                .checked_div(BPS_DENOMINATOR as u128)
+                .ok_or(LotteryError::ArithmeticError)? as u64
+        }
+        PrizeKind::FixedAmount => tier.value.min(total_pool),
+    };
+    
+    Ok(amount)
 }
 
 #[inline]
@@ -88,13 +163,68 @@ pub fn validate_min_pool(lottery_type: LotteryType, current_pool: u64) -> Result
 }
 
 #[inline]
-pub fn count_matching_digits(user_numbers: &[u8; 6], winning_numbers: &[u8; 6]) -> u8 {
+pub fn count_matching_digits(user_numbers: &[u8; 6], winning_numbers: &[u8; 6], picks: u8) -> u8 {
     user_numbers.iter()
         .zip(winning_numbers.iter())
+        .take(picks as usize)
         .filter(|(a, b)| a == b)
         .count() as u8
 }
 
+#[inline]
+pub fn count_matching_unordered(user_numbers: &[u8; 6], winning_numbers: &[u8; 6], picks: u8) -> u8 {
+    let winning = &winning_numbers[..picks as usize];
+    user_numbers[..picks as usize]
+        .iter()
+        .filter(|n| winning.contains(n))
+        .count() as u8
+}
+
+#[inline]
+pub fn count_matches(mode: MatchMode, user_numbers: &[u8; 6], winning_numbers: &[u8; 6], picks: u8) -> u8 {
+    match mode {
+        MatchMode::Positional => count_matching_digits(user_numbers, winning_numbers, picks),
+        MatchMode::Unordered => count_matching_unordered(user_numbers, winning_numbers, picks),
+    }
+}
+
+/// Matches, bonus result and prize tier index of a ticket against the draw.
+pub fn evaluate_ticket(lottery: &Lottery, ticket: &Ticket) -> (u8, bool, Option<usize>) {
+    let matching = count_matches(
+        lottery.config.match_mode,
+        &ticket.numbers,
+        &lottery.winning_numbers,
+        lottery.config.number_domain.picks,
+    );
+    let bonus_matched = lottery.bonus_number.is_some() && ticket.bonus_number == lottery.bonus_number;
+    let tier = lottery.config.prize_tiers.position(matching, bonus_matched);
+    (matching, bonus_matched, tier)
+}
+
+/// Even split of a tier's total among its registered winners.
+pub fn calculate_tier_share(tier_amount: u64, winners: u32) -> Result<u64> {
+    require!(winners > 0, LotteryError::InvalidPrizeClaim);
+    let share = tier_amount
+        .checked_div(winners as u64)
+        .ok_or(LotteryError::ArithmeticError)?;
+    Ok(share)
+}
+
+/// One registered ticket's payout for the tier at `tier_index`.
+pub fn tier_share(lottery: &Lottery, tier_index: usize) -> Result<u64> {
+    let tier = lottery.config.prize_tiers.active()
+        .get(tier_index)
+        .ok_or(LotteryError::InvalidPrizeClaim)?;
+    let tier_amount = calculate_prize_amount(tier, lottery.state_data.prize_amount)?;
+    calculate_tier_share(tier_amount, lottery.state_data.tier_winners[tier_index])
+}
+
+#[inline]
+pub fn is_registration_open(draw_timestamp: i64) -> Result<bool> {
+    let current_time = get_current_timestamp()?;
+    Ok(current_time <= draw_timestamp + REGISTRATION_WINDOW)
+}
+
 #[inline]
 pub fn is_claim_window_expired(draw_timestamp: i64) -> Result<bool> {
     let current_time = get_current_timestamp()?;
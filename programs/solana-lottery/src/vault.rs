@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use crate::state::lottery::Lottery;
+
+// Every lottery holds its tokens in a vault token account at
+// `[VAULT_SEED, lottery]`, created with the lottery and owned by the lottery
+// PDA. Its address and mint are pinned in `LotteryConfig`. Tokens can only
+// leave through `transfer_from_vault`, which signs with the lottery's own
+// seeds.
+
+pub const VAULT_SEED: &[u8] = b"vault";
+
+/// Moves `amount` out of the lottery vault, signed by the lottery PDA.
+pub fn transfer_from_vault<'info>(
+    lottery: &Account<'info, Lottery>,
+    vault: &Account<'info, TokenAccount>,
+    to: AccountInfo<'info>,
+    token_program: &Program<'info, Token>,
+    amount: u64,
+) -> Result<()> {
+    let id_bytes = lottery.id.to_le_bytes();
+    let seeds: &[&[u8]] = &[b"lottery", lottery.authority.as_ref(), id_bytes.as_ref(), &[lottery.bump]];
+    token::transfer(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            Transfer {
+                from: vault.to_account_info(),
+                to,
+                authority: lottery.to_account_info(),
+            },
+            &[seeds],
+        ),
+        amount
+    )
+}
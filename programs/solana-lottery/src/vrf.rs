@@ -0,0 +1,386 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::spl_token_2022::solana_zk_token_sdk::curve25519::{
+    edwards::{multiply_edwards, subtract_edwards, validate_edwards, PodEdwardsPoint},
+    scalar::PodScalar,
+};
+use crate::errors::LotteryError;
+use sha2::{Sha256, Sha512, Digest};
+
+// Verifiable randomness from a registered oracle key.
+//
+// Proofs follow ECVRF-EDWARDS25519-SHA512-TAI (RFC 9381) and are checked
+// on-chain with the curve25519 syscalls. For a given key and `alpha` every
+// valid proof yields the same output, so the oracle can withhold a draw but
+// cannot pick among several results.
+//
+// Local testing: create the lottery with a test key as `vrf_oracle` and
+// prove `alpha` with any RFC 9381 implementation of this suite; the tests
+// below carry a minimal prover.
+
+const VRF_ALPHA_DOMAIN: &[u8] = b"solana-lottery:vrf-alpha";
+
+pub const PROOF_SIZE: usize = 80; // Gamma (32) || c (16) || s (32)
+pub const OUTPUT_SIZE: usize = 64;
+
+const SUITE: u8 = 0x03;
+const CHALLENGE_SIZE: usize = 16;
+
+const BASEPOINT: PodEdwardsPoint = PodEdwardsPoint([
+    0x58, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66,
+    0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66,
+]);
+const IDENTITY: PodEdwardsPoint = PodEdwardsPoint([
+    0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+]);
+const COFACTOR: PodScalar = PodScalar([
+    0x08, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+]);
+
+// Prime order of the basepoint, little-endian
+const GROUP_ORDER: [u8; 32] = [
+    0xed, 0xd3, 0xf5, 0x5c, 0x1a, 0x63, 0x12, 0x58, 0xd6, 0x9c, 0xf7, 0xa2, 0xde, 0xf9, 0xde, 0x14,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x10,
+];
+
+/// Deterministic VRF input for a round. Every component is fixed once the
+/// lottery enters `Drawing`, so the oracle cannot be asked for a different one.
+pub fn compute_alpha(lottery: &Pubkey, lottery_id: u64, total_tickets: u64, end_time: i64) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(VRF_ALPHA_DOMAIN);
+    hasher.update(lottery.as_ref());
+    hasher.update(lottery_id.to_le_bytes());
+    hasher.update(total_tickets.to_le_bytes());
+    hasher.update(end_time.to_le_bytes());
+    hasher.finalize().into()
+}
+
+pub(crate) fn is_canonical_scalar(bytes: &[u8; 32]) -> bool {
+    for (byte, order) in bytes.iter().zip(GROUP_ORDER.iter()).rev() {
+        if byte != order {
+            return byte < order;
+        }
+    }
+    false
+}
+
+/// RFC 8032 point decoding. The syscalls also accept encodings with
+/// `y >= p` or a signed zero `x`, which RFC 9381 requires us to reject.
+fn decode_point(bytes: &[u8; 32]) -> Option<PodEdwardsPoint> {
+    let mut y = *bytes;
+    let sign = y[31] >> 7;
+    y[31] &= 0x7f;
+
+    let high_bytes_full = y[1..31].iter().all(|b| *b == 0xff) && y[31] == 0x7f;
+    let y_too_large = high_bytes_full && y[0] >= 0xed;
+    // x is zero only for y = 1 and y = p - 1
+    let x_is_zero = (y[0] == 0x01 && y[1..].iter().all(|b| *b == 0))
+        || (high_bytes_full && y[0] == 0xec);
+    if y_too_large || (x_is_zero && sign == 1) {
+        return None;
+    }
+
+    let point = PodEdwardsPoint(*bytes);
+    validate_edwards(&point).then_some(point)
+}
+
+#[inline]
+fn multiply(scalar: &PodScalar, point: &PodEdwardsPoint) -> Result<PodEdwardsPoint> {
+    Ok(multiply_edwards(scalar, point).ok_or(LotteryError::InvalidVrfProof)?)
+}
+
+#[inline]
+fn subtract(left: &PodEdwardsPoint, right: &PodEdwardsPoint) -> Result<PodEdwardsPoint> {
+    Ok(subtract_edwards(left, right).ok_or(LotteryError::InvalidVrfProof)?)
+}
+
+/// ECVRF_encode_to_curve_try_and_increment, salted with the public key.
+pub(crate) fn encode_to_curve(public_key: &PodEdwardsPoint, alpha: &[u8]) -> Result<PodEdwardsPoint> {
+    for ctr in 0..=u8::MAX {
+        let hash = Sha512::new()
+            .chain_update([SUITE, 0x01])
+            .chain_update(public_key.0)
+            .chain_update(alpha)
+            .chain_update([ctr, 0x00])
+            .finalize();
+
+        let mut candidate = [0u8; 32];
+        candidate.copy_from_slice(&hash[..32]);
+        if let Some(point) = decode_point(&candidate) {
+            return multiply(&COFACTOR, &point);
+        }
+    }
+    err!(LotteryError::InvalidVrfProof)
+}
+
+pub(crate) fn challenge(points: [&PodEdwardsPoint; 5]) -> [u8; CHALLENGE_SIZE] {
+    let mut hasher = Sha512::new();
+    hasher.update([SUITE, 0x02]);
+    for point in points {
+        hasher.update(point.0);
+    }
+    hasher.update([0x00]);
+
+    let mut c = [0u8; CHALLENGE_SIZE];
+    c.copy_from_slice(&hasher.finalize()[..CHALLENGE_SIZE]);
+    c
+}
+
+pub(crate) fn proof_to_hash(gamma: &PodEdwardsPoint) -> Result<[u8; OUTPUT_SIZE]> {
+    let cofactor_gamma = multiply(&COFACTOR, gamma)?;
+    let hash = Sha512::new()
+        .chain_update([SUITE, 0x03])
+        .chain_update(cofactor_gamma.0)
+        .chain_update([0x00])
+        .finalize();
+    Ok(hash.into())
+}
+
+/// Verifies `proof` for `alpha` under `oracle` and returns the VRF output
+/// (`beta` in RFC 9381).
+pub fn verify_proof(
+    oracle: &Pubkey,
+    alpha: &[u8],
+    proof: &[u8; PROOF_SIZE],
+) -> Result<[u8; OUTPUT_SIZE]> {
+    let public_key = decode_point(&oracle.to_bytes()).ok_or(LotteryError::InvalidVrfProof)?;
+    // Low-order keys would let any Gamma verify
+    require!(
+        multiply(&COFACTOR, &public_key)? != IDENTITY,
+        LotteryError::InvalidVrfProof
+    );
+
+    let mut gamma_bytes = [0u8; 32];
+    gamma_bytes.copy_from_slice(&proof[..32]);
+    let gamma = decode_point(&gamma_bytes).ok_or(LotteryError::InvalidVrfProof)?;
+
+    let mut c = [0u8; 32];
+    c[..CHALLENGE_SIZE].copy_from_slice(&proof[32..32 + CHALLENGE_SIZE]);
+    let mut s = [0u8; 32];
+    s.copy_from_slice(&proof[32 + CHALLENGE_SIZE..]);
+    require!(is_canonical_scalar(&s), LotteryError::InvalidVrfProof);
+    let (c, s) = (PodScalar(c), PodScalar(s));
+
+    let h = encode_to_curve(&public_key, alpha)?;
+
+    // U = s*B - c*Y, V = s*H - c*Gamma
+    let u = subtract(&multiply(&s, &BASEPOINT)?, &multiply(&c, &public_key)?)?;
+    let v = subtract(&multiply(&s, &h)?, &multiply(&c, &gamma)?)?;
+
+    let expected = challenge([&public_key, &h, &gamma, &u, &v]);
+    require!(
+        expected[..] == proof[32..32 + CHALLENGE_SIZE],
+        LotteryError::InvalidVrfProof
+    );
+
+    proof_to_hash(&gamma)
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    const ONE: [u8; 32] = {
+        let mut one = [0u8; 32];
+        one[0] = 1;
+        one
+    };
+
+    // Bit-serial arithmetic modulo the group order: slow, but enough to
+    // prove in tests without pulling in a scalar library.
+    fn add_mod(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+        let mut sum = [0u8; 32];
+        let mut carry = 0u16;
+        for i in 0..32 {
+            let t = a[i] as u16 + b[i] as u16 + carry;
+            sum[i] = t as u8;
+            carry = t >> 8;
+        }
+        if is_canonical_scalar(&sum) {
+            return sum;
+        }
+        let mut borrow = 0i16;
+        for i in 0..32 {
+            let t = sum[i] as i16 - GROUP_ORDER[i] as i16 - borrow;
+            sum[i] = t.rem_euclid(256) as u8;
+            borrow = (t < 0) as i16;
+        }
+        sum
+    }
+
+    /// Little-endian bytes of any length, reduced modulo the group order.
+    fn reduce(bytes: &[u8]) -> [u8; 32] {
+        mul_add(bytes, &ONE, &[0u8; 32])
+    }
+
+    /// `a * b + c` modulo the group order.
+    fn mul_add(a: &[u8], b: &[u8; 32], c: &[u8; 32]) -> [u8; 32] {
+        let mut result = [0u8; 32];
+        for byte in a.iter().rev() {
+            for bit in (0..8).rev() {
+                result = add_mod(&result, &result);
+                if (byte >> bit) & 1 == 1 {
+                    result = add_mod(&result, b);
+                }
+            }
+        }
+        add_mod(&result, c)
+    }
+
+    pub(crate) struct TestOracle {
+        secret: [u8; 32],
+        pub public_key: Pubkey,
+    }
+
+    impl TestOracle {
+        pub(crate) fn new(seed: &[u8]) -> Self {
+            let secret = reduce(&Sha512::digest(seed));
+            let public_key = multiply_edwards(&PodScalar(secret), &BASEPOINT).unwrap();
+            Self {
+                secret,
+                public_key: Pubkey::new_from_array(public_key.0),
+            }
+        }
+
+        /// RFC 9381 ECVRF_prove; `nonce_seed` stands in for the hashed secret
+        /// key so tests can produce different proofs for the same input.
+        pub(crate) fn prove_with_nonce(&self, alpha: &[u8], nonce_seed: &[u8]) -> [u8; PROOF_SIZE] {
+            let public_key = PodEdwardsPoint(self.public_key.to_bytes());
+            let h = encode_to_curve(&public_key, alpha).unwrap();
+            let gamma = multiply_edwards(&PodScalar(self.secret), &h).unwrap();
+
+            let k = reduce(&Sha512::new().chain_update(nonce_seed).chain_update(h.0).finalize());
+            let k_b = multiply_edwards(&PodScalar(k), &BASEPOINT).unwrap();
+            let k_h = multiply_edwards(&PodScalar(k), &h).unwrap();
+            let c = challenge([&public_key, &h, &gamma, &k_b, &k_h]);
+            let s = mul_add(&c, &self.secret, &k);
+
+            let mut proof = [0u8; PROOF_SIZE];
+            proof[..32].copy_from_slice(&gamma.0);
+            proof[32..48].copy_from_slice(&c);
+            proof[48..].copy_from_slice(&s);
+            proof
+        }
+
+        pub(crate) fn prove(&self, alpha: &[u8]) -> [u8; PROOF_SIZE] {
+            self.prove_with_nonce(alpha, &self.secret)
+        }
+    }
+
+    fn hex<const N: usize>(s: &str) -> [u8; N] {
+        let mut bytes = [0u8; N];
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            *byte = u8::from_str_radix(&s[2 * i..2 * i + 2], 16).unwrap();
+        }
+        bytes
+    }
+
+    #[test]
+    fn rfc_9381_example_16() {
+        let public_key = hex("d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a");
+        let proof = hex(concat!(
+            "8657106690b5526245a92b003bb079ccd1a92130477671f6fc01ad16f26f723f",
+            "26f8a57ccaed74ee1b190bed1f479d97",
+            "27d2d0f9b005a6e456a35d4fb0daab1268a1b0db10836d9826a528ca76567805",
+        ));
+        let beta = hex(concat!(
+            "90cf1df3b703cce59e2a35b925d411164068269d7b2d29f3301c03dd757876ff",
+            "66b71dda49d2de59d03450451af026798e8f81cd2e333de5cdf4f3e140fdd8ae",
+        ));
+        assert_eq!(verify_proof(&Pubkey::new_from_array(public_key), b"", &proof).unwrap(), beta);
+    }
+
+    fn alpha() -> [u8; 32] {
+        compute_alpha(&Pubkey::new_from_array([7u8; 32]), 3, 150, 1_700_000_000)
+    }
+
+    #[test]
+    fn valid_proof_verifies() {
+        let oracle = TestOracle::new(b"oracle");
+        let proof = oracle.prove(&alpha());
+        let output = verify_proof(&oracle.public_key, &alpha(), &proof).unwrap();
+
+        let mut gamma = [0u8; 32];
+        gamma.copy_from_slice(&proof[..32]);
+        assert_eq!(output, proof_to_hash(&PodEdwardsPoint(gamma)).unwrap());
+    }
+
+    #[test]
+    fn output_does_not_depend_on_nonce() {
+        let oracle = TestOracle::new(b"oracle");
+        let first = oracle.prove_with_nonce(&alpha(), b"nonce-1");
+        let second = oracle.prove_with_nonce(&alpha(), b"nonce-2");
+        assert_ne!(first, second);
+        assert_eq!(
+            verify_proof(&oracle.public_key, &alpha(), &first).unwrap(),
+            verify_proof(&oracle.public_key, &alpha(), &second).unwrap()
+        );
+    }
+
+    #[test]
+    fn rejects_wrong_oracle() {
+        let oracle = TestOracle::new(b"oracle");
+        let other = TestOracle::new(b"other");
+        let proof = oracle.prove(&alpha());
+        assert!(verify_proof(&other.public_key, &alpha(), &proof).is_err());
+    }
+
+    #[test]
+    fn rejects_tampered_alpha() {
+        let oracle = TestOracle::new(b"oracle");
+        let proof = oracle.prove(&alpha());
+        let mut tampered = alpha();
+        tampered[0] ^= 1;
+        assert!(verify_proof(&oracle.public_key, &tampered, &proof).is_err());
+    }
+
+    #[test]
+    fn rejects_tampered_proof() {
+        let oracle = TestOracle::new(b"oracle");
+        let proof = oracle.prove(&alpha());
+        for index in [0, 40, 60] {
+            let mut tampered = proof;
+            tampered[index] ^= 1;
+            assert!(verify_proof(&oracle.public_key, &alpha(), &tampered).is_err());
+        }
+    }
+
+    #[test]
+    fn rejects_non_canonical_s() {
+        let oracle = TestOracle::new(b"oracle");
+        let mut proof = oracle.prove(&alpha());
+        // s + L encodes the same scalar but is not canonical
+        let mut carry = 0u16;
+        for i in 0..32 {
+            let t = proof[48 + i] as u16 + GROUP_ORDER[i] as u16 + carry;
+            proof[48 + i] = t as u8;
+            carry = t >> 8;
+        }
+        assert!(verify_proof(&oracle.public_key, &alpha(), &proof).is_err());
+    }
+
+    #[test]
+    fn rejects_non_canonical_points() {
+        // y = p encodes the same point as y = 0
+        let mut y_is_p = [0xffu8; 32];
+        y_is_p[0] = 0xed;
+        y_is_p[31] = 0x7f;
+        assert!(decode_point(&y_is_p).is_none());
+
+        // Identity with the sign bit set
+        let mut signed_identity = IDENTITY.0;
+        signed_identity[31] |= 0x80;
+        assert!(decode_point(&signed_identity).is_none());
+
+        assert!(decode_point(&IDENTITY.0).is_some());
+        assert!(decode_point(&BASEPOINT.0).is_some());
+    }
+
+    #[test]
+    fn rejects_low_order_key() {
+        let oracle = Pubkey::new_from_array(IDENTITY.0);
+        let proof = TestOracle::new(b"oracle").prove(&alpha());
+        assert!(verify_proof(&oracle, &alpha(), &proof).is_err());
+    }
+}
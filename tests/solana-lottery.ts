@@ -1,125 +1,506 @@
-import * as anchor from "@project-serum/anchor";
-import { Program } from "@project-serum/anchor";
+import * as anchor from "@coral-xyz/anchor";
+import { AnchorError, BN, Program } from "@coral-xyz/anchor";
 import { SolanaLottery } from "../target/types/solana_lottery";
 import { expect } from "chai";
-import { PublicKey, Keypair, SystemProgram } from "@solana/web3.js";
+import { createHash } from "crypto";
+import {
+  Keypair,
+  PublicKey,
+  SystemProgram,
+  Transaction,
+  TransactionInstruction,
+} from "@solana/web3.js";
+
+const TOKEN_PROGRAM_ID = anchor.utils.token.TOKEN_PROGRAM_ID;
+const BPF_LOADER_UPGRADEABLE_ID = new PublicKey(
+  "BPFLoaderUpgradeab1e11111111111111111111111"
+);
+// Pyth SOL/USD price account, cloned from mainnet in Anchor.toml
+const PYTH_SOL_USD = new PublicKey(
+  "H6ARHf6YXhGYeQfUzQNGk6rDNnLBQKrenN712K4AQJEG"
+);
+const USDC = 1_000_000;
+const TICKET_PRICE = 1 * USDC; // daily lottery
+const BOND = 10 * USDC;
+
+const u64 = (value: number | BN) =>
+  new BN(value).toArrayLike(Buffer, "le", 8);
+
+async function expectError(promise: Promise<unknown>, code: string) {
+  try {
+    await promise;
+  } catch (err) {
+    expect(err).to.be.instanceOf(AnchorError);
+    expect((err as AnchorError).error.errorCode.code).to.equal(code);
+    return;
+  }
+  expect.fail(`expected ${code}`);
+}
 
 describe("solana-lottery", () => {
   const provider = anchor.AnchorProvider.env();
   anchor.setProvider(provider);
 
   const program = anchor.workspace.SolanaLottery as Program<SolanaLottery>;
-  const authority = Keypair.generate();
-  const treasury = Keypair.generate();
-  
-  before(async () => {
-    // Fund authority
-    const signature = await provider.connection.requestAirdrop(
-      authority.publicKey,
-      2 * anchor.web3.LAMPORTS_PER_SOL
-    );
-    await provider.connection.confirmTransaction(signature);
-  });
+  // The local validator deploys with the provider wallet as upgrade authority
+  const admin = provider.wallet as anchor.Wallet;
+  const buyer = Keypair.generate();
+  const stranger = Keypair.generate();
+  const mint = Keypair.generate();
+  const otherMint = Keypair.generate();
 
-  it("Creates a new lottery", async () => {
-    const lottery = Keypair.generate();
-    const ticketPrice = new anchor.BN(0.1 * anchor.web3.LAMPORTS_PER_SOL);
-    
-    await program.methods
-      .createLottery(ticketPrice)
-      .accounts({
-        lottery: lottery.publicKey,
-        authority: authority.publicKey,
-        treasury: treasury.publicKey,
-        systemProgram: SystemProgram.programId,
-      })
-      .signers([lottery, authority])
-      .rpc();
+  const pda = (...seeds: Buffer[]) =>
+    PublicKey.findProgramAddressSync(seeds, program.programId)[0];
 
-    const lotteryAccount = await program.account.lottery.fetch(lottery.publicKey);
-    expect(lotteryAccount.authority.toString()).to.equal(authority.publicKey.toString());
-    expect(lotteryAccount.ticketPrice.toString()).to.equal(ticketPrice.toString());
-  });
+  const config = pda(Buffer.from("config"));
+  const treasury = pda(Buffer.from("treasury"));
+  const treasuryVault = pda(Buffer.from("treasury_vault"), treasury.toBuffer());
+  const series = pda(Buffer.from("series"), admin.publicKey.toBuffer());
+  const roleAddress = (role: number, holder: PublicKey) =>
+    pda(Buffer.from("role"), Buffer.from([role]), holder.toBuffer());
+  const operatorRole = roleAddress(1, admin.publicKey);
+  const drawerRole = roleAddress(2, admin.publicKey);
+  const [programData] = PublicKey.findProgramAddressSync(
+    [program.programId.toBuffer()],
+    BPF_LOADER_UPGRADEABLE_ID
+  );
+
+  let adminTokens: PublicKey;
+  let buyerTokens: PublicKey;
+  let lottery: PublicKey;
+  let lotteryVault: PublicKey;
+  let ticket: PublicKey;
 
-  it("Buys a ticket", async () => {
-    const buyer = Keypair.generate();
-    const ticket = Keypair.generate();
-    
-    // Fund buyer
-    const signature = await provider.connection.requestAirdrop(
-      buyer.publicKey,
-      anchor.web3.LAMPORTS_PER_SOL
+  // Minimal SPL Token instructions, so the tests need no token client
+  async function createMint(mint: Keypair, decimals: number) {
+    const space = 82;
+    const tx = new Transaction().add(
+      SystemProgram.createAccount({
+        fromPubkey: admin.publicKey,
+        newAccountPubkey: mint.publicKey,
+        space,
+        lamports: await provider.connection.getMinimumBalanceForRentExemption(space),
+        programId: TOKEN_PROGRAM_ID,
+      }),
+      new TransactionInstruction({
+        programId: TOKEN_PROGRAM_ID,
+        keys: [{ pubkey: mint.publicKey, isSigner: false, isWritable: true }],
+        // InitializeMint2, no freeze authority
+        data: Buffer.concat([
+          Buffer.from([20, decimals]),
+          admin.publicKey.toBuffer(),
+          Buffer.from([0]),
+        ]),
+      })
     );
-    await provider.connection.confirmTransaction(signature);
-    
-    await program.methods
-      .buyTicket()
-      .accounts({
-        lottery: lottery.publicKey,
-        ticket: ticket.publicKey,
-        buyer: buyer.publicKey,
-        treasury: treasury.publicKey,
-        systemProgram: SystemProgram.programId,
+    await provider.sendAndConfirm(tx, [mint]);
+  }
+
+  async function createFundedTokenAccount(owner: PublicKey, amount: number) {
+    const account = Keypair.generate();
+    const space = 165;
+    const tx = new Transaction().add(
+      SystemProgram.createAccount({
+        fromPubkey: admin.publicKey,
+        newAccountPubkey: account.publicKey,
+        space,
+        lamports: await provider.connection.getMinimumBalanceForRentExemption(space),
+        programId: TOKEN_PROGRAM_ID,
+      }),
+      new TransactionInstruction({
+        programId: TOKEN_PROGRAM_ID,
+        keys: [
+          { pubkey: account.publicKey, isSigner: false, isWritable: true },
+          { pubkey: mint.publicKey, isSigner: false, isWritable: false },
+        ],
+        // InitializeAccount3
+        data: Buffer.concat([Buffer.from([18]), owner.toBuffer()]),
+      }),
+      new TransactionInstruction({
+        programId: TOKEN_PROGRAM_ID,
+        keys: [
+          { pubkey: mint.publicKey, isSigner: false, isWritable: true },
+          { pubkey: account.publicKey, isSigner: false, isWritable: true },
+          { pubkey: admin.publicKey, isSigner: true, isWritable: false },
+        ],
+        // MintTo
+        data: Buffer.concat([Buffer.from([7]), u64(amount)]),
       })
-      .signers([buyer, ticket])
-      .rpc();
+    );
+    await provider.sendAndConfirm(tx, [account]);
+    return account.publicKey;
+  }
 
-    const ticketAccount = await program.account.ticket.fetch(ticket.publicKey);
-    expect(ticketAccount.owner.toString()).to.equal(buyer.publicKey.toString());
+  async function tokenBalance(account: PublicKey) {
+    const { value } = await provider.connection.getTokenAccountBalance(account);
+    return Number(value.amount);
+  }
+
+  before(async () => {
+    for (const wallet of [buyer, stranger]) {
+      const signature = await provider.connection.requestAirdrop(
+        wallet.publicKey,
+        2 * anchor.web3.LAMPORTS_PER_SOL
+      );
+      await provider.connection.confirmTransaction(signature);
+    }
+
+    await createMint(mint, 6);
+    await createMint(otherMint, 6);
+    adminTokens = await createFundedTokenAccount(admin.publicKey, 100 * USDC);
+    buyerTokens = await createFundedTokenAccount(buyer.publicKey, 10 * USDC);
   });
 
-  it("Schedules a draw", async () => {
-    await program.methods
-      .scheduleDraw()
-      .accounts({
-        lottery: lottery.publicKey,
-        authority: authority.publicKey,
-      })
-      .signers([authority])
-      .rpc();
+  describe("protocol setup", () => {
+    it("rejects config initialization by anyone but the upgrade authority", async () => {
+      await expectError(
+        program.methods
+          .initializeConfig({
+            feeBps: 250,
+            allowedMints: [mint.publicKey],
+            oracles: [PYTH_SOL_USD],
+            governanceDelay: null,
+          })
+          .accountsPartial({
+            admin: stranger.publicKey,
+            program: program.programId,
+            programData,
+            config,
+          })
+          .signers([stranger])
+          .rpc(),
+        "Unauthorized"
+      );
+    });
+
+    it("initializes the config", async () => {
+      await program.methods
+        .initializeConfig({
+          feeBps: 250,
+          allowedMints: [mint.publicKey],
+          oracles: [PYTH_SOL_USD],
+          governanceDelay: null,
+        })
+        .accountsPartial({
+          admin: admin.publicKey,
+          program: program.programId,
+          programData,
+          config,
+        })
+        .rpc();
+
+      const account = await program.account.protocolConfig.fetch(config);
+      expect(account.admin.toBase58()).to.equal(admin.publicKey.toBase58());
+      expect(account.feeBps).to.equal(250);
+      expect(account.mintCount).to.equal(1);
+      expect(account.oracleCount).to.equal(1);
+    });
+
+    it("initializes the treasury and its fee vault", async () => {
+      await program.methods
+        .initializeTreasury({ signers: [admin.publicKey], threshold: 1 })
+        .accountsPartial({
+          authority: admin.publicKey,
+          config,
+          treasury,
+          treasuryTokenAccount: treasuryVault,
+          mint: mint.publicKey,
+        })
+        .rpc();
+
+      const account = await program.account.treasury.fetch(treasury);
+      expect(account.tokenAccount.toBase58()).to.equal(treasuryVault.toBase58());
+      expect(account.threshold).to.equal(1);
+    });
+
+    it("grants operator and drawer roles", async () => {
+      await program.methods
+        .grantRole({ operator: {} }, admin.publicKey)
+        .accountsPartial({
+          admin: admin.publicKey,
+          config,
+          adminRole: null,
+          assignment: operatorRole,
+        })
+        .rpc();
+      await program.methods
+        .grantRole({ drawer: {} }, admin.publicKey)
+        .accountsPartial({
+          admin: admin.publicKey,
+          config,
+          adminRole: null,
+          assignment: drawerRole,
+        })
+        .rpc();
+
+      const account = await program.account.roleAssignment.fetch(operatorRole);
+      expect(account.holder.toBase58()).to.equal(admin.publicKey.toBase58());
+    });
+
+    it("rejects role grants from non-admins", async () => {
+      await expectError(
+        program.methods
+          .grantRole({ operator: {} }, stranger.publicKey)
+          .accountsPartial({
+            admin: stranger.publicKey,
+            config,
+            adminRole: null,
+            assignment: roleAddress(1, stranger.publicKey),
+          })
+          .signers([stranger])
+          .rpc(),
+        "Unauthorized"
+      );
+    });
 
-    const lotteryAccount = await program.account.lottery.fetch(lottery.publicKey);
-    expect(lotteryAccount.status).to.equal("DRAW_SCHEDULED");
+    it("initializes the operator's series", async () => {
+      await program.methods
+        .initializeSeries()
+        .accountsPartial({
+          authority: admin.publicKey,
+          operatorRole,
+          series,
+        })
+        .rpc();
+
+      const account = await program.account.lotterySeries.fetch(series);
+      expect(account.nextRound.toNumber()).to.equal(0);
+    });
   });
 
-  it("Processes VRF callback", async () => {
-    const vrf = Keypair.generate();
-    
-    await program.methods
-      .processVrfCallback()
-      .accounts({
-        lottery: lottery.publicKey,
-        vrf: vrf.publicKey,
-        authority: authority.publicKey,
-      })
-      .signers([authority, vrf])
-      .rpc();
+  describe("lottery round", () => {
+    it("creates a commit-reveal lottery and takes the bond", async () => {
+      const { nextRound } = await program.account.lotterySeries.fetch(series);
+      lottery = pda(Buffer.from("lottery"), admin.publicKey.toBuffer(), u64(nextRound));
+      lotteryVault = pda(Buffer.from("vault"), lottery.toBuffer());
+
+      await program.methods
+        .createLottery({
+          lotteryType: { daily: {} },
+          startTime: null,
+          maxPriceAge: null,
+          numberDomain: null,
+          matchMode: { positional: {} },
+          bonusDomain: null,
+          randomness: { commitReveal: {} },
+          vrfOracle: null,
+          bondAmount: new BN(BOND),
+        })
+        .accountsPartial({
+          admin: admin.publicKey,
+          operatorRole,
+          config,
+          series,
+          lottery,
+          pythPriceFeed: PYTH_SOL_USD,
+          treasury,
+          treasuryTokenAccount: treasuryVault,
+          prizeTable: null,
+          mint: mint.publicKey,
+          lotteryTokenAccount: lotteryVault,
+          adminTokenAccount: adminTokens,
+        })
+        .rpc();
+
+      const account = await program.account.lottery.fetch(lottery);
+      expect(account.state).to.deep.equal({ created: {} });
+      expect(account.config.ticketPrice.toNumber()).to.equal(TICKET_PRICE);
+      expect(account.config.feeBps).to.equal(250);
+      expect(account.config.vault.toBase58()).to.equal(lotteryVault.toBase58());
+      expect(await tokenBalance(lotteryVault)).to.equal(BOND);
+    });
+
+    it("rejects a lottery on an allowed mint the treasury vault cannot hold", async () => {
+      await program.methods
+        .updateConfig({
+          admin: null,
+          allowedMints: [mint.publicKey, otherMint.publicKey],
+          paused: null,
+        })
+        .accountsPartial({ admin: admin.publicKey, config })
+        .rpc();
+
+      const { nextRound } = await program.account.lotterySeries.fetch(series);
+      const other = pda(Buffer.from("lottery"), admin.publicKey.toBuffer(), u64(nextRound));
+
+      await expectError(
+        program.methods
+          .createLottery({
+            lotteryType: { daily: {} },
+            startTime: null,
+            maxPriceAge: null,
+            numberDomain: null,
+            matchMode: { positional: {} },
+            bonusDomain: null,
+            randomness: { priceFeed: {} },
+            vrfOracle: null,
+            bondAmount: new BN(0),
+          })
+          .accountsPartial({
+            admin: admin.publicKey,
+            operatorRole,
+            config,
+            series,
+            lottery: other,
+            pythPriceFeed: PYTH_SOL_USD,
+            treasury,
+            treasuryTokenAccount: treasuryVault,
+            prizeTable: null,
+            mint: otherMint.publicKey,
+            lotteryTokenAccount: pda(Buffer.from("vault"), other.toBuffer()),
+            adminTokenAccount: null,
+          })
+          .rpc(),
+        "InvalidMint"
+      );
+    });
+
+    it("opens the lottery", async () => {
+      await program.methods
+        .openLottery()
+        .accountsPartial({ lottery, authority: admin.publicKey, operatorRole })
+        .rpc();
+
+      const account = await program.account.lottery.fetch(lottery);
+      expect(account.state).to.deep.equal({ open: {} });
+    });
+
+    it("does not schedule a draw before the round ends", async () => {
+      const secret = Buffer.alloc(32, 7);
+      const commitment = createHash("sha256").update(secret).digest();
+
+      await expectError(
+        program.methods
+          .scheduleDraw(Array.from(commitment))
+          .accountsPartial({ lottery, admin: admin.publicKey, drawerRole, config })
+          .rpc(),
+        "InvalidTimeRange"
+      );
+    });
+
+    it("sells a ticket into the vault", async () => {
+      const { id, stateData } = await program.account.lottery.fetch(lottery);
+      ticket = pda(
+        Buffer.from("ticket"),
+        lottery.toBuffer(),
+        u64(id),
+        u64(stateData.totalTickets)
+      );
 
-    const lotteryAccount = await program.account.lottery.fetch(lottery.publicKey);
-    expect(lotteryAccount.vrfVerified).to.be.true;
+      await program.methods
+        .buyTicket([1, 2, 3, 4, 5, 6], null)
+        .accountsPartial({
+          buyer: buyer.publicKey,
+          config,
+          lottery,
+          ticket,
+          buyerTokenAccount: buyerTokens,
+          lotteryTokenAccount: lotteryVault,
+        })
+        .signers([buyer])
+        .rpc();
+
+      const account = await program.account.ticket.fetch(ticket);
+      expect(account.owner.toBase58()).to.equal(buyer.publicKey.toBase58());
+      expect(account.numbers).to.deep.equal([1, 2, 3, 4, 5, 6]);
+      expect(await tokenBalance(buyerTokens)).to.equal(10 * USDC - TICKET_PRICE);
+      expect(await tokenBalance(lotteryVault)).to.equal(BOND + TICKET_PRICE);
+    });
+
+    it("rejects numbers outside the lottery's domain", async () => {
+      const { id, stateData } = await program.account.lottery.fetch(lottery);
+      await expectError(
+        program.methods
+          .buyTicket([1, 2, 3, 4, 5, 10], null)
+          .accountsPartial({
+            buyer: buyer.publicKey,
+            config,
+            lottery,
+            ticket: pda(
+              Buffer.from("ticket"),
+              lottery.toBuffer(),
+              u64(id),
+              u64(stateData.totalTickets)
+            ),
+            buyerTokenAccount: buyerTokens,
+            lotteryTokenAccount: lotteryVault,
+          })
+          .signers([buyer])
+          .rpc(),
+        "InvalidTicketNumbers"
+      );
+    });
+
+    it("only lets the lottery authority or config admin cancel", async () => {
+      await expectError(
+        program.methods
+          .cancelLottery({ oracleOutage: {} })
+          .accountsPartial({ lottery, authority: stranger.publicKey, config })
+          .signers([stranger])
+          .rpc(),
+        "Unauthorized"
+      );
+    });
+
+    it("cancels an open round into refunds", async () => {
+      await program.methods
+        .cancelLottery({ oracleOutage: {} })
+        .accountsPartial({ lottery, authority: admin.publicKey, config })
+        .rpc();
+
+      const account = await program.account.lottery.fetch(lottery);
+      expect(account.state).to.deep.equal({ refunding: {} });
+      expect(account.cancelReason).to.deep.equal({ oracleOutage: {} });
+      expect(account.timing.refundDeadline.toNumber()).to.be.greaterThan(0);
+    });
+
+    it("refunds the ticket price once", async () => {
+      const refund = () =>
+        program.methods
+          .claimRefund()
+          .accountsPartial({
+            owner: buyer.publicKey,
+            lottery,
+            ticket,
+            ownerTokenAccount: buyerTokens,
+            lotteryTokenAccount: lotteryVault,
+          })
+          .signers([buyer])
+          .rpc();
+
+      await refund();
+      expect(await tokenBalance(buyerTokens)).to.equal(10 * USDC);
+      expect((await program.account.ticket.fetch(ticket)).refunded).to.be.true;
+
+      await expectError(refund(), "TicketAlreadyRefunded");
+    });
   });
 
-  it("Withdraws from treasury with timelock", async () => {
-    // Wait for timelock
-    await new Promise(resolve => setTimeout(resolve, 1000));
-    
-    const destination = Keypair.generate();
-    const additionalSigner = Keypair.generate();
-    const amount = new anchor.BN(0.05 * anchor.web3.LAMPORTS_PER_SOL);
-
-    await program.methods
-      .withdrawTreasury(amount)
-      .accounts({
-        treasury: treasury.publicKey,
-        authority: authority.publicKey,
-        destination: destination.publicKey,
-        additionalSigner: additionalSigner.publicKey,
-        systemProgram: SystemProgram.programId,
-      })
-      .signers([authority, additionalSigner])
-      .rpc();
+  describe("governance", () => {
+    it("holds fee changes until the governance delay has passed", async () => {
+      const { nextProposalId } = await program.account.protocolConfig.fetch(config);
+      const proposal = pda(Buffer.from("proposal"), u64(nextProposalId));
+
+      await program.methods
+        .queueProposal({ feeBps: [300] })
+        .accountsPartial({ admin: admin.publicKey, config, proposal })
+        .rpc();
+
+      await expectError(
+        program.methods
+          .executeProposal()
+          .accountsPartial({
+            config,
+            proposal,
+            lottery: null,
+            prizeTable: null,
+          })
+          .rpc(),
+        "ProposalNotReady"
+      );
 
-    const treasuryAccount = await program.account.treasury.fetch(treasury.publicKey);
-    expect(treasuryAccount.balance.toString()).to.equal("0");
+      const account = await program.account.protocolConfig.fetch(config);
+      expect(account.feeBps).to.equal(250);
+    });
   });
-}); 
\ No newline at end of file
+});